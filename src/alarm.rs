@@ -0,0 +1,459 @@
+// ====================================
+// ===== Alarms
+// ====================================
+
+use std::{
+    collections::VecDeque,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use time::{Date, OffsetDateTime, Weekday};
+
+use crate::cli::AlarmAction;
+use crate::config::{data_dir, modified};
+use tock::zone::Zone;
+
+const DAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+const WEEKDAYS: u8 = 0b001_1111;
+const WEEKENDS: u8 = 0b110_0000;
+const DAILY: u8 = WEEKDAYS | WEEKENDS;
+
+/// On which days an alarm goes off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// The next time the clock shows the alarm time; removed afterwards.
+    Once,
+    /// Bit `n` set means the n-th day counted from Monday.
+    Days(u8),
+}
+
+impl Repeat {
+    /// `once`, `daily`, `weekdays` or `weekends`.
+    pub fn keyword(s: &str) -> Option<Repeat> {
+        match s.to_ascii_lowercase().as_str() {
+            "once" => Some(Repeat::Once),
+            "daily" => Some(Repeat::Days(DAILY)),
+            "weekdays" => Some(Repeat::Days(WEEKDAYS)),
+            "weekends" => Some(Repeat::Days(WEEKENDS)),
+            _ => None,
+        }
+    }
+
+    /// A comma separated list of `daily`, `weekdays`, `weekends` and day
+    /// names (`mon,wed,fri`, at least three letters each), as after `every`.
+    pub fn days(s: &str) -> Option<Repeat> {
+        let s = s.to_ascii_lowercase();
+        let mut mask = 0;
+        for part in s.split(',') {
+            mask |= match Repeat::keyword(part) {
+                Some(Repeat::Days(days)) => days,
+                _ => {
+                    let n = DAYS
+                        .iter()
+                        .position(|name| part.len() >= 3 && name.starts_with(part))?;
+                    1 << n
+                }
+            };
+        }
+        Some(Repeat::Days(mask))
+    }
+
+    pub fn matches(self, weekday: Weekday) -> bool {
+        match self {
+            Repeat::Once => true,
+            Repeat::Days(mask) => mask & (1 << weekday.number_days_from_monday()) != 0,
+        }
+    }
+}
+
+impl fmt::Display for Repeat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Repeat::Once => f.write_str("once"),
+            Repeat::Days(DAILY) => f.write_str("daily"),
+            Repeat::Days(WEEKDAYS) => f.write_str("weekdays"),
+            Repeat::Days(WEEKENDS) => f.write_str("weekends"),
+            Repeat::Days(mask) => {
+                let days: Vec<&str> = (0..7)
+                    .filter(|n| mask & (1 << n) != 0)
+                    .map(|n| &DAYS[n][..3])
+                    .collect();
+                write!(f, "every {}", days.join(","))
+            }
+        }
+    }
+}
+
+/// One alarm as written on the command line, in the input dialog and in the
+/// state file: `HH:MM [REPEAT] [LABEL]`. Day names only count as the repeat
+/// after `every`, so `07:30 Monday meeting` goes off once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alarm {
+    pub hour: u8,
+    pub minute: u8,
+    pub repeat: Repeat,
+    pub label: String,
+}
+
+impl Alarm {
+    pub fn parse(s: &str) -> Result<Alarm, String> {
+        let mut words = s.split_whitespace();
+        let time = words.next().ok_or_else(|| {
+            "expected HH:MM [once|daily|weekdays|weekends|every mon,wed,...] [LABEL]".to_string()
+        })?;
+        let (hour, minute) = time
+            .split_once(':')
+            .and_then(|(h, m)| Some((h.parse::<u8>().ok()?, m.parse::<u8>().ok()?)))
+            .filter(|&(h, m)| h < 24 && m < 60)
+            .ok_or_else(|| format!("expected a time like 07:30, got '{}'", time))?;
+        let rest: Vec<&str> = words.collect();
+        let (repeat, used) = match rest.as_slice() {
+            [every, days, ..] if every.eq_ignore_ascii_case("every") => (Repeat::days(days), 2),
+            [word, ..] => (Repeat::keyword(word), 1),
+            [] => (None, 0),
+        };
+        let (repeat, label) = match repeat {
+            Some(repeat) => (repeat, rest[used..].join(" ")),
+            None => (Repeat::Once, rest.join(" ")),
+        };
+        Ok(Alarm {
+            hour,
+            minute,
+            repeat,
+            label,
+        })
+    }
+
+    pub fn time(&self) -> String {
+        format!("{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl fmt::Display for Alarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // always spell out the repeat so a label starting with a day name
+        // reads back unchanged
+        write!(f, "{} {}", self.time(), self.repeat)?;
+        if !self.label.is_empty() {
+            write!(f, " {}", self.label)?;
+        }
+        Ok(())
+    }
+}
+
+/// `$XDG_DATA_HOME/tock/alarms`, else `~/.local/share/tock/alarms`.
+pub fn default_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("alarms"))
+}
+
+/// Reads the state file, one alarm per line; `#` starts a comment line and
+/// a missing file has no alarms.
+pub fn load(path: &Path) -> Result<Vec<Alarm>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    text.lines()
+        .enumerate()
+        .map(|(n, line)| (n, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(n, line)| {
+            Alarm::parse(line).map_err(|e| format!("{}:{}: {}", path.display(), n + 1, e))
+        })
+        .collect()
+}
+
+pub fn save(path: &Path, alarms: &[Alarm]) -> Result<(), String> {
+    let mut text =
+        String::from("# HH:MM [once|daily|weekdays|weekends|every mon,wed,...] [LABEL]\n");
+    for alarm in alarms {
+        text.push_str(&format!("{}\n", alarm));
+    }
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(path, text))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// `tock alarm add|list|remove`.
+pub fn run(action: AlarmAction, path: &Path) -> Result<(), String> {
+    let mut alarms = load(path)?;
+    match action {
+        AlarmAction::Add { words } => {
+            let alarm = Alarm::parse(&words.join(" "))?;
+            println!("added {}", alarm);
+            alarms.push(alarm);
+            save(path, &alarms)?;
+        }
+        AlarmAction::List => {
+            if alarms.is_empty() {
+                println!("no alarms in {}", path.display());
+            }
+            for (n, alarm) in alarms.iter().enumerate() {
+                println!("{:>3}  {}", n + 1, alarm);
+            }
+        }
+        AlarmAction::Remove { number } => {
+            if number == 0 || number > alarms.len() {
+                return Err(format!(
+                    "no alarm number {} ({} alarms)",
+                    number,
+                    alarms.len()
+                ));
+            }
+            println!("removed {}", alarms.remove(number - 1));
+            save(path, &alarms)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs the configured hook through the shell without waiting for it. The
+/// alarm is passed as `$TOCK_ALARM`, `$TOCK_ALARM_TIME` and
+/// `$TOCK_ALARM_LABEL`; output is discarded so it cannot garble the screen.
+pub fn run_hook(command: &str, alarm: &Alarm) -> io::Result<()> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = process::Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .env("TOCK_ALARM", alarm.to_string())
+        .env("TOCK_ALARM_TIME", alarm.time())
+        .env("TOCK_ALARM_LABEL", &alarm.label)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // reap it in the background so it does not linger as a zombie
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// An alarm that went off and waits to be snoozed or dismissed.
+#[derive(Debug, Clone)]
+pub struct Ringing {
+    pub alarm: Alarm,
+    pub since: Instant,
+    rung: Option<Instant>,
+}
+
+impl Ringing {
+    pub const FLASH: Duration = Duration::from_millis(500);
+
+    /// Alternates every `FLASH`, for flashing the dial.
+    pub fn flash(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.since);
+        (elapsed.as_nanos() / Self::FLASH.as_nanos()).is_multiple_of(2)
+    }
+}
+
+/// The alarms of the state file plus what is ringing or snoozed right now.
+/// Picks up changes to the file, e.g. from `tock alarm add` in another
+/// terminal.
+pub struct Alarms {
+    path: Option<PathBuf>,
+    list: Vec<Alarm>,
+    modified: Option<SystemTime>,
+    checked: Option<Instant>,
+    minute: Option<(Date, u8, u8)>,
+    snoozed: Vec<(Alarm, OffsetDateTime)>,
+    /// Went off while another was ringing; they ring in turn.
+    waiting: VecDeque<Alarm>,
+    ringing: Option<Ringing>,
+}
+
+impl Alarms {
+    const INTERVAL: Duration = Duration::from_millis(500);
+    /// How long the bell keeps ringing; the dial flashes until dismissed.
+    const BELL_FOR: Duration = Duration::from_secs(60);
+    const BELL_EVERY: Duration = Duration::from_secs(2);
+
+    /// Nothing is read until the first `poll`.
+    pub fn new(path: Option<PathBuf>) -> Alarms {
+        Alarms {
+            path,
+            list: Vec::new(),
+            modified: None,
+            checked: None,
+            minute: None,
+            snoozed: Vec::new(),
+            waiting: VecDeque::new(),
+            ringing: None,
+        }
+    }
+
+    /// Reloads the state file if it changed since the last call.
+    pub fn poll(&mut self) -> Option<Result<(), String>> {
+        let path = self.path.as_ref()?;
+        if self
+            .checked
+            .is_some_and(|checked| checked.elapsed() < Self::INTERVAL)
+        {
+            return None;
+        }
+        self.checked = Some(Instant::now());
+        let modified = modified(path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(load(path).map(|list| self.list = list))
+    }
+
+    pub fn add(&mut self, alarm: Alarm) -> Result<(), String> {
+        self.list.push(alarm);
+        self.save()
+    }
+
+    fn save(&mut self) -> Result<(), String> {
+        match &self.path {
+            Some(path) => {
+                save(path, &self.list)?;
+                self.modified = modified(path);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    pub fn ringing(&self) -> Option<&Ringing> {
+        self.ringing.as_ref()
+    }
+
+    /// Starts ringing when the wall clock in `zone` enters the minute of an
+    /// alarm or a snooze runs out, and returns the alarm that went off.
+    /// One alarm rings at a time; others that are due wait until it is
+    /// snoozed or dismissed. Alarms set to go off once are removed from the
+    /// file. `instant` is when ringing started, for the bell and the flashing.
+    pub fn check(
+        &mut self,
+        now: OffsetDateTime,
+        instant: Instant,
+        zone: &Zone,
+    ) -> Result<Option<Alarm>, String> {
+        let mut removed = false;
+        let local = zone.at(now);
+        let minute = (local.date(), local.hour(), local.minute());
+        if self.minute != Some(minute) {
+            self.minute = Some(minute);
+            let due = |alarm: &Alarm| {
+                alarm.hour == minute.1
+                    && alarm.minute == minute.2
+                    && alarm.repeat.matches(local.weekday())
+            };
+            self.waiting
+                .extend(self.list.iter().filter(|alarm| due(alarm)).cloned());
+            let count = self.list.len();
+            self.list
+                .retain(|alarm| alarm.repeat != Repeat::Once || !due(alarm));
+            removed = self.list.len() != count;
+        }
+        let over = self.snoozed.extract_if(.., |(_, until)| now >= *until);
+        self.waiting.extend(over.map(|(alarm, _)| alarm));
+        let fired = match self.ringing {
+            Some(_) => None,
+            None => self.waiting.pop_front(),
+        };
+        if let Some(alarm) = &fired {
+            self.ringing = Some(Ringing {
+                alarm: alarm.clone(),
+                since: instant,
+                rung: None,
+            });
+        }
+        if removed {
+            self.save()?;
+        }
+        Ok(fired)
+    }
+
+    /// True when the bell should ring again for the current alarm.
+    pub fn bell_due(&mut self, now: Instant) -> bool {
+        match &mut self.ringing {
+            Some(ringing)
+                if now.saturating_duration_since(ringing.since) < Self::BELL_FOR
+                    && ringing.rung.is_none_or(|rung| {
+                        now.saturating_duration_since(rung) >= Self::BELL_EVERY
+                    }) =>
+            {
+                ringing.rung = Some(now);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Silences the ringing alarm and lets it go off again after `delay`.
+    pub fn snooze(&mut self, now: OffsetDateTime, delay: Duration) {
+        if let Some(ringing) = self.ringing.take() {
+            self.snoozed.push((ringing.alarm, now + delay));
+        }
+    }
+
+    pub fn dismiss(&mut self) {
+        self.ringing = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn day_names_need_every() {
+        let alarm = Alarm::parse("07:30 Monday meeting").unwrap();
+        assert_eq!(alarm.repeat, Repeat::Once);
+        assert_eq!(alarm.label, "Monday meeting");
+
+        let alarm = Alarm::parse("07:30 every Monday meeting").unwrap();
+        assert_eq!(alarm.repeat, Repeat::Days(1));
+        assert_eq!(alarm.label, "meeting");
+        assert_eq!(alarm.to_string(), "07:30 every mon meeting");
+        assert_eq!(Alarm::parse(&alarm.to_string()), Ok(alarm));
+
+        let alarm = Alarm::parse("07:30 weekends Coffee").unwrap();
+        assert_eq!(
+            (alarm.repeat, alarm.label.as_str()),
+            (Repeat::Days(WEEKENDS), "Coffee")
+        );
+        let alarm = Alarm::parse("07:30 every other week").unwrap();
+        assert_eq!(alarm.label, "every other week");
+    }
+
+    #[test]
+    fn alarms_in_the_same_minute_ring_in_turn() {
+        let (now, instant) = (datetime!(2024-03-11 07:30:00 UTC), Instant::now());
+        let mut alarms = Alarms::new(None);
+        alarms.add(Alarm::parse("07:30 Tea").unwrap()).unwrap();
+        alarms.add(Alarm::parse("07:30 Toast").unwrap()).unwrap();
+
+        let first = alarms.check(now, instant, &Zone::Utc).unwrap();
+        assert_eq!(first.unwrap().label, "Tea");
+        assert!(alarms.list.is_empty(), "both are removed once due");
+        // the second waits while the first rings
+        let later = now + Duration::from_secs(10);
+        assert_eq!(alarms.check(later, instant, &Zone::Utc), Ok(None));
+        alarms.dismiss();
+        let second = alarms.check(later, instant, &Zone::Utc).unwrap();
+        assert_eq!(second.unwrap().label, "Toast");
+        alarms.dismiss();
+        assert_eq!(alarms.check(later, instant, &Zone::Utc), Ok(None));
+    }
+}
@@ -0,0 +1,617 @@
+// ====================================
+// ===== Application state
+// ====================================
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::KeyCode;
+use time::OffsetDateTime;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders},
+    Frame,
+};
+
+use crate::alarm::{self, Alarm, Alarms, Ringing};
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::countdown::Countdown;
+use crate::cycle::Cycle;
+use crate::history;
+use crate::keys::{Key, Scope};
+use crate::overlay::{self, FrameCounter, Prompt};
+use crate::stopwatch::Stopwatch;
+use crate::world::WorldClock;
+use tock::complication::Complications;
+use tock::dial::{self, Dial, DialStyle, Face, Motion};
+use tock::source::TimeSource;
+use tock::theme::{ColorDepth, Fallback, Theme};
+use tock::zone::Zone;
+use tock::ClockWidget;
+
+/// What the centre of the screen shows.
+pub enum Mode {
+    Clock,
+    World(WorldClock),
+    Stopwatch(Stopwatch),
+    Timer(Countdown),
+    Cycle(Cycle),
+}
+
+impl Mode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Clock => "Clock",
+            Mode::World(_) => "World",
+            Mode::Stopwatch(_) => "Stopwatch",
+            Mode::Timer(_) => "Timer",
+            Mode::Cycle(_) => "Cycle",
+        }
+    }
+}
+
+/// Everything the main loop keeps between frames.
+pub struct App {
+    pub mode: Mode,
+    /// The other modes the mode key goes through, in order. They keep
+    /// running while parked.
+    pub parked: Vec<Mode>,
+    /// Flags win over the config, also after a reload.
+    pub cli: Cli,
+    pub config: Config,
+    pub config_error: Option<String>,
+    pub zone: Zone,
+    /// Set by `--theme` or the theme key, else the config picks.
+    pub theme_name: Option<String>,
+    pub theme: Theme,
+    /// Colours beyond this are drawn as the nearest one it has.
+    pub color_depth: ColorDepth,
+    pub dial: Dial,
+    pub motion: Motion,
+    pub complications: Complications,
+    /// Size of the centre box in cells, before clipping to the terminal.
+    pub center_width: u16,
+    pub center_height: u16,
+    pub lock_aspect: bool,
+    pub alarms: Alarms,
+    pub alarm_error: Option<String>,
+    pub history_path: Option<PathBuf>,
+    pub history_error: Option<String>,
+    pub prompt: Option<Prompt>,
+    pub help: bool,
+    /// Set by `--frames`.
+    pub frame_counter: Option<FrameCounter>,
+    pub quit: bool,
+    pub exit_code: i32,
+}
+
+impl App {
+    /// No state files are touched until `alarms` and `history_path` are set.
+    pub fn new(cli: &Cli, config: Config, mode: Mode) -> App {
+        let theme = config.theme(cli.theme.as_deref()).unwrap_or_default();
+        let parked = match mode {
+            Mode::Clock => vec![Mode::Stopwatch(Stopwatch::new())],
+            Mode::Stopwatch(_) => vec![Mode::Clock],
+            _ => vec![Mode::Clock, Mode::Stopwatch(Stopwatch::new())],
+        };
+        App {
+            mode,
+            parked,
+            cli: cli.clone(),
+            theme_name: cli.theme.clone(),
+            zone: cli.zone.or(config.zone).unwrap_or_else(Zone::local),
+            theme,
+            color_depth: cli.color_depth(),
+            dial: Dial::new(cli.style(config.style(&theme))),
+            motion: cli.motion(),
+            complications: config.complications.complications(),
+            center_width: cli
+                .size
+                .map(|size| size.width)
+                .or(config.width)
+                .unwrap_or(80),
+            center_height: cli
+                .size
+                .map(|size| size.height)
+                .or(config.height)
+                .unwrap_or(40),
+            lock_aspect: false,
+            config,
+            config_error: None,
+            alarms: Alarms::new(None),
+            alarm_error: None,
+            history_path: None,
+            history_error: None,
+            prompt: None,
+            help: false,
+            frame_counter: None,
+            quit: false,
+            exit_code: 0,
+        }
+    }
+
+    /// Applies a reloaded config; a broken one keeps the old settings.
+    pub fn reload(&mut self, reloaded: Result<Config, String>) {
+        let (new, theme) = match reloaded.and_then(|new| {
+            let theme = new.theme(self.theme_name.as_deref())?;
+            Ok((new, theme))
+        }) {
+            Ok(new) => new,
+            Err(e) => {
+                self.config_error = Some(e);
+                return;
+            }
+        };
+        if self.cli.size.is_none() {
+            if new.width != self.config.width {
+                self.center_width = new.width.unwrap_or(self.center_width);
+            }
+            if new.height != self.config.height {
+                self.center_height = new.height.unwrap_or(self.center_height);
+            }
+        }
+        let face_changed = new.marks.face != self.config.marks.face;
+        if new.complications != self.config.complications {
+            self.complications = new.complications.complications();
+        }
+        self.config = new;
+        self.config_error = None;
+        self.zone = self
+            .cli
+            .zone
+            .or(self.config.zone)
+            .unwrap_or_else(Zone::local);
+        for mode in std::iter::once(&mut self.mode).chain(&mut self.parked) {
+            if let Mode::World(world) = mode {
+                world.local = self.zone;
+            }
+        }
+        // keep a face picked with the key unless the file picks another
+        let face = (!face_changed).then_some(self.dial.style.face);
+        self.restyle(theme, face);
+    }
+
+    /// Switches to `theme`, keeping colours set by the config and flags and
+    /// `face` if given.
+    fn restyle(&mut self, theme: Theme, face: Option<Face>) {
+        self.theme = theme;
+        let mut style = self.cli.style(self.config.style(&theme));
+        if let Some(face) = face {
+            style.face = face;
+        }
+        self.dial = Dial::new(style);
+    }
+
+    /// The theme after the current one: the built-in ones, then those from
+    /// the config.
+    fn next_theme(&mut self) {
+        let current = self
+            .theme_name
+            .clone()
+            .or_else(|| self.config.theme.clone())
+            .unwrap_or_else(|| "dark".to_string());
+        let names: Vec<String> = Theme::NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .chain(self.config.themes.keys().cloned())
+            .collect();
+        let next = match names.iter().position(|name| *name == current) {
+            Some(i) => names[(i + 1) % names.len()].clone(),
+            None => names[0].clone(),
+        };
+        match self.config.theme(Some(&next)) {
+            Ok(theme) => {
+                self.theme_name = Some(next);
+                self.restyle(theme, Some(self.dial.style.face));
+            }
+            Err(e) => self.config_error = Some(e),
+        }
+    }
+
+    /// The stopwatch, whether shown or parked.
+    pub fn stopwatch(&self) -> Option<&Stopwatch> {
+        std::iter::once(&self.mode)
+            .chain(&self.parked)
+            .find_map(|mode| match mode {
+                Mode::Stopwatch(stopwatch) => Some(stopwatch),
+                _ => None,
+            })
+    }
+
+    /// Handles one key press; `term` is the size of the whole terminal.
+    pub fn key(&mut self, code: KeyCode, term: Rect, source: &dyn TimeSource) {
+        let keys = &self.config.keys.clone();
+        let key = Key(code);
+        let now = source.instant();
+        if let Some(input) = &mut self.prompt {
+            match code {
+                KeyCode::Esc => self.prompt = None,
+                KeyCode::Enter => match Alarm::parse(&input.text) {
+                    Ok(alarm) => {
+                        self.alarm_error = self.alarms.add(alarm).err();
+                        self.prompt = None;
+                    }
+                    Err(e) => input.error = Some(e),
+                },
+                code => input.edit(code),
+            }
+            return;
+        }
+        // the help stays up while the keys it lists are tried out
+        if self.help && (code == KeyCode::Esc || key == keys.help) {
+            self.help = false;
+            return;
+        }
+        if self.alarms.ringing().is_some() {
+            if key == keys.snooze {
+                self.alarms.snooze(source.now(), self.config.alarm.snooze);
+                return;
+            }
+            if key == keys.dismiss {
+                self.alarms.dismiss();
+                return;
+            }
+        }
+
+        match &mut self.mode {
+            Mode::Stopwatch(stopwatch) => match key {
+                k if k == keys.start_stop => stopwatch.toggle(now),
+                k if k == keys.lap => stopwatch.lap(now),
+                k if k == keys.reset => stopwatch.reset(),
+                _ => {}
+            },
+            Mode::Timer(countdown) => match key {
+                k if k == keys.start_stop => countdown.toggle(now),
+                k if k == keys.add_minute => countdown.add(Duration::from_secs(60), now),
+                _ => {}
+            },
+            Mode::Cycle(cycle) => match key {
+                k if k == keys.start_stop => cycle.toggle(now),
+                k if k == keys.skip => cycle.skip(now),
+                _ => {}
+            },
+            _ => {}
+        }
+        if let Some(complication) = keys.complication(key) {
+            self.complications.toggle(complication);
+        }
+        match key {
+            k if k == keys.quit => {
+                // a countdown or program that did not run out counts as cancelled
+                for mode in std::iter::once(&self.mode).chain(&self.parked) {
+                    match mode {
+                        Mode::Timer(_) => self.exit_code = 1,
+                        Mode::Cycle(cycle) if !cycle.program().repeat => self.exit_code = 1,
+                        _ => {}
+                    }
+                }
+                self.quit = true;
+            }
+            k if k == keys.help => self.help = true,
+            k if k == keys.mode => {
+                self.parked
+                    .push(std::mem::replace(&mut self.mode, Mode::Clock));
+                self.mode = self.parked.remove(0);
+            }
+            k if k == keys.theme => self.next_theme(),
+            k if k == keys.sweep => self.motion = self.motion.toggle(),
+            k if k == keys.face => {
+                self.dial = Dial::new(DialStyle {
+                    face: self.dial.style.face.next(),
+                    ..self.dial.style
+                })
+            }
+            k if k == keys.add_alarm => self.prompt = Some(Prompt::default()),
+            k if k == keys.lock_aspect => {
+                self.lock_aspect = !self.lock_aspect;
+                if self.lock_aspect {
+                    self.center_width = dial::width_for(self.center_height);
+                }
+            }
+            k if k == keys.taller && self.center_height < term.height => self.center_height += 1,
+            k if k == keys.shorter => self.center_height = self.center_height.saturating_sub(1),
+            k if k == keys.wider && self.center_width < term.width => self.center_width += 1,
+            k if k == keys.narrower => self.center_width = self.center_width.saturating_sub(1),
+            _ => {}
+        }
+        if self.lock_aspect {
+            if key == keys.taller || key == keys.shorter {
+                self.center_width = dial::width_for(self.center_height);
+            } else if key == keys.wider || key == keys.narrower {
+                self.center_height = dial::height_for(self.center_width);
+            }
+        }
+    }
+
+    /// Advances timers, cycles and alarms; true when the bell should ring.
+    pub fn tick(&mut self, source: &dyn TimeSource) -> bool {
+        let mut bell = false;
+        for mode in std::iter::once(&mut self.mode).chain(&mut self.parked) {
+            match mode {
+                Mode::Timer(countdown) if countdown.is_done(source.instant()) => {
+                    bell = true;
+                    self.quit = true;
+                }
+                Mode::Cycle(cycle) => {
+                    if let Some(done) = cycle.tick(source.instant()) {
+                        bell = true;
+                        let entry = history::Entry {
+                            finished: source.now(),
+                            program: cycle.program().name.clone(),
+                            kind: done.kind,
+                            duration: done.duration,
+                        };
+                        if let Some(path) = &self.history_path {
+                            self.history_error = history::append(path, &entry)
+                                .err()
+                                .map(|e| format!("{}: {}", path.display(), e));
+                        }
+                    }
+                    if cycle.is_finished() {
+                        self.quit = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        match self
+            .alarms
+            .check(source.now(), source.instant(), &self.zone)
+        {
+            Ok(Some(alarm)) => {
+                if let Some(command) = &self.config.alarm.command {
+                    if let Err(e) = alarm::run_hook(command, &alarm) {
+                        self.alarm_error = Some(format!("{}: {}", command, e));
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => self.alarm_error = Some(e),
+        }
+        bell || self.alarms.bell_due(source.instant())
+    }
+
+    /// How long the frame for `now` and `instant` stays correct, in the time
+    /// of the source they came from; `None` when only input changes it.
+    pub fn next_change(&self, now: OffsetDateTime, instant: Instant) -> Option<Duration> {
+        const SECOND: Duration = Duration::from_secs(1);
+        // readouts show tenths of a second
+        const TENTH: Duration = Duration::from_millis(100);
+        let sweep = self.motion == Motion::Sweep;
+        let mode = match &self.mode {
+            Mode::Clock | Mode::World(_) if sweep => Some(Duration::ZERO),
+            Mode::Clock | Mode::World(_) => {
+                Some(SECOND - Duration::from_nanos(now.nanosecond() as u64))
+            }
+            Mode::Stopwatch(stopwatch) if stopwatch.is_running() => Some(if sweep {
+                Duration::ZERO
+            } else {
+                until_next(stopwatch.elapsed(instant), TENTH)
+            }),
+            Mode::Timer(countdown) if countdown.is_running() => Some(if sweep {
+                Duration::ZERO
+            } else {
+                until_next_down(countdown.remaining(instant), TENTH)
+            }),
+            Mode::Cycle(cycle) if cycle.countdown().is_running() => {
+                Some(until_next_down(cycle.countdown().remaining(instant), TENTH))
+            }
+            _ => None,
+        };
+        let flash = self.alarms.ringing().map(|ringing| {
+            until_next(
+                instant.saturating_duration_since(ringing.since),
+                Ringing::FLASH,
+            )
+        });
+        match (mode, flash) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Draws one frame showing `now`; modes that measure time use `instant`.
+    pub fn draw<B: Backend>(&self, f: &mut Frame<B>, now: OffsetDateTime, instant: Instant) {
+        let size = f.size();
+        let dial_area = if let Mode::World(world) = &self.mode {
+            let block = Block::default().title("Tock - World").borders(Borders::ALL);
+            let inner = block.inner(size);
+            f.render_widget(block, size);
+            world.render(f, inner, &self.dial, now, self.motion, self.complications);
+            inner
+        } else {
+            let center = self.center(size);
+            match &self.mode {
+                Mode::Stopwatch(stopwatch) => {
+                    stopwatch.render(f, center, &self.dial, instant, self.motion, &self.theme)
+                }
+                Mode::Timer(countdown) => {
+                    countdown.render(f, center, &self.dial, instant, self.motion, &self.theme)
+                }
+                Mode::Cycle(cycle) => cycle.render(f, center, &self.dial, instant, &self.theme),
+                _ => {
+                    let title = format!("Tock - {}", self.zone.name());
+                    let clock = ClockWidget::new()
+                        .dial(&self.dial)
+                        .time(now)
+                        .zone(self.zone)
+                        .motion(self.motion)
+                        .complications(self.complications)
+                        .block(Block::default().title(title).borders(Borders::ALL));
+                    f.render_widget(clock, center);
+                }
+            }
+            center
+        };
+
+        let keys = &self.config.keys;
+        if let Some(ringing) = self.alarms.ringing() {
+            if ringing.flash(instant) {
+                let flash = Block::default().style(Style::default().bg(self.theme.flash));
+                f.render_widget(flash, dial_area);
+            }
+            let message = format!(
+                "{} {}\n{} snooze, {} dismiss",
+                ringing.alarm.time(),
+                ringing.alarm.label,
+                keys.snooze,
+                keys.dismiss
+            );
+            overlay::notice(f, size, &self.theme, "Alarm", &message);
+        }
+        if let Some(input) = &self.prompt {
+            let hint = "HH:MM [once|daily|weekdays|weekends|every mon,wed,...] [LABEL]";
+            input.render(
+                f,
+                size,
+                &self.theme,
+                "New alarm (enter add, esc cancel)",
+                hint,
+            );
+        }
+
+        if self.help {
+            let ringing = self.alarms.ringing().is_some();
+            let bindings: Vec<(String, &str)> = keys
+                .actions()
+                .into_iter()
+                .filter(|&(_, scope, _, _)| match scope {
+                    Scope::Everywhere => true,
+                    Scope::Stopwatch => matches!(self.mode, Mode::Stopwatch(_)),
+                    Scope::Timer => matches!(self.mode, Mode::Timer(_)),
+                    Scope::Cycle => matches!(self.mode, Mode::Cycle(_)),
+                    Scope::Alarm => ringing,
+                })
+                .map(|(_, _, description, key)| (key.to_string(), description))
+                .chain([
+                    ("ctrl-c".to_string(), "quit at once"),
+                    ("ctrl-z".to_string(), "suspend"),
+                ])
+                .collect();
+            let title = format!("Keys - {} ({} or esc closes)", self.mode.name(), keys.help);
+            overlay::help(f, size, &self.theme, &title, &bindings);
+        }
+
+        if let Some(counter) = &self.frame_counter {
+            counter.render(f, size);
+        }
+
+        if let Some(e) = &self.config_error {
+            overlay::error(f, size, &self.theme, "Config error", e);
+        } else if let Some(e) = &self.history_error {
+            overlay::error(f, size, &self.theme, "History error", e);
+        } else if let Some(e) = &self.alarm_error {
+            overlay::error(f, size, &self.theme, "Alarm error", e);
+        }
+
+        f.render_widget(Fallback(self.color_depth), size);
+    }
+
+    /// The centre box, clipped to `size` and centred in it.
+    fn center(&self, size: Rect) -> Rect {
+        let width = size.width.min(self.center_width);
+        let height = size.height.min(self.center_height);
+        let top_btm = (size.height - height) / 2;
+        let left_right = (size.width - width) / 2;
+
+        let lay_vert = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(top_btm),
+                Constraint::Length(height),
+                Constraint::Length(top_btm),
+            ])
+            .split(size);
+        let lay_hori = Layout::default()
+            .direction(Direction::Horizontal)
+            .margin(1)
+            .constraints([
+                Constraint::Length(left_right),
+                Constraint::Length(width),
+                Constraint::Length(left_right),
+            ])
+            .split(lay_vert[1]);
+        lay_hori[1]
+    }
+}
+
+/// Time until `elapsed` reaches the next multiple of `step`.
+fn until_next(elapsed: Duration, step: Duration) -> Duration {
+    let step = step.as_nanos();
+    Duration::from_nanos((step - elapsed.as_nanos() % step) as u64)
+}
+
+/// Time until `remaining`, counting down, drops to the next multiple of
+/// `step`.
+fn until_next_down(remaining: Duration, step: Duration) -> Duration {
+    match remaining.as_nanos() % step.as_nanos() {
+        0 => step,
+        rest => Duration::from_nanos(rest as u64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use time::macros::datetime;
+    use tock::source::FixedClock;
+
+    fn app(args: &[&str], mode: Mode) -> App {
+        let cli = Cli::try_parse_from(["tock", "--zone", "UTC"].iter().chain(args)).unwrap();
+        App::new(&cli, Config::default(), mode)
+    }
+
+    #[test]
+    fn ticking_clock_redraws_on_the_second() {
+        let now = datetime!(2024-03-10 10:08:37.25 UTC);
+        let instant = Instant::now();
+        let wait = app(&[], Mode::Clock).next_change(now, instant);
+        assert_eq!(wait, Some(Duration::from_millis(750)));
+        let wait = app(&["--sweep"], Mode::Clock).next_change(now, instant);
+        assert_eq!(wait, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn idle_clock_redraws_at_most_once_a_second() {
+        // plays the main loop with no input: draw, then sleep until the next change
+        let mut source = FixedClock::new(datetime!(2024-03-10 10:08:37.25 UTC));
+        let end = source.now() + time::Duration::minutes(1);
+        let mut app = app(&[], Mode::Clock);
+        let mut frames = 0;
+        while source.now() < end {
+            assert!(!app.tick(&source));
+            frames += 1;
+            let wait = app.next_change(source.now(), source.instant()).unwrap();
+            assert!(wait > Duration::ZERO && wait <= Duration::from_secs(1));
+            source.advance(wait);
+            assert_eq!(source.now().nanosecond(), 0);
+        }
+        // the first frame plus one per second boundary
+        assert_eq!(frames, 61);
+    }
+
+    #[test]
+    fn stopped_modes_only_redraw_on_input() {
+        let now = datetime!(2024-03-10 10:08:37 UTC);
+        let instant = Instant::now();
+        let stopwatch = app(&[], Mode::Stopwatch(Stopwatch::new()));
+        assert_eq!(stopwatch.next_change(now, instant), None);
+
+        let mut countdown = Countdown::new(Duration::from_millis(1_250), instant);
+        let running = app(&[], Mode::Timer(countdown.clone()));
+        assert_eq!(
+            running.next_change(now, instant),
+            Some(Duration::from_millis(50))
+        );
+        countdown.toggle(instant);
+        let paused = app(&[], Mode::Timer(countdown));
+        assert_eq!(paused.next_change(now, instant), None);
+    }
+}
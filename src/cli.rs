@@ -0,0 +1,529 @@
+// ====================================
+// ===== Command line
+// ====================================
+
+use std::{path::PathBuf, time::Duration};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Deserialize;
+use tui::{style::Color, symbols::Marker};
+
+use crate::cycle::{parse_interval, Program};
+use crate::stopwatch::ExportFormat;
+use tock::dial::{DialStyle, Face, Motion};
+use tock::hand::HandShape;
+use tock::source::{ScaledClock, SystemClock, TimeSource};
+use tock::theme::ColorDepth;
+use tock::zone::Zone;
+
+/// An analog clock for the terminal.
+#[derive(Debug, Clone, Parser)]
+#[command(name = "tock", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Time zone: an IANA name (Europe/Berlin), UTC or an offset (+05:30) [default: system zone]
+    #[arg(short, long, global = true, value_parser = Zone::parse)]
+    pub zone: Option<Zone>,
+
+    /// Config file [default: ~/.config/tock/config.toml]
+    #[arg(short, long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Initial dial size in terminal cells [default: 80x40]
+    #[arg(long, global = true, value_name = "WIDTHxHEIGHT", value_parser = parse_size)]
+    pub size: Option<Size>,
+
+    /// Colour theme: dark, light, solarized, high-contrast, monochrome or one
+    /// from [themes] in the config [default: dark]
+    #[arg(long, global = true, value_name = "NAME")]
+    pub theme: Option<String>,
+
+    /// Colours the terminal can show [default: from $COLORTERM and $TERM]
+    #[arg(long, global = true, value_enum)]
+    pub colors: Option<ColorsArg>,
+
+    /// Hour hand colour: a name (red, light-blue, ...), #rrggbb or a 0-255 palette index [default: red]
+    #[arg(long, global = true, value_name = "COLOR", value_parser = parse_color)]
+    pub hour_color: Option<Color>,
+
+    /// Minute hand colour [default: green]
+    #[arg(long, global = true, value_name = "COLOR", value_parser = parse_color)]
+    pub minute_color: Option<Color>,
+
+    /// Second hand colour [default: blue]
+    #[arg(long, global = true, value_name = "COLOR", value_parser = parse_color)]
+    pub second_color: Option<Color>,
+
+    /// Hour mark colour [default: white]
+    #[arg(long, global = true, value_name = "COLOR", value_parser = parse_color)]
+    pub mark_color: Option<Color>,
+
+    /// Hour hand shape [default: line]
+    #[arg(long, global = true, value_enum, value_name = "SHAPE")]
+    pub hour_shape: Option<ShapeArg>,
+
+    /// Minute hand shape [default: line]
+    #[arg(long, global = true, value_enum, value_name = "SHAPE")]
+    pub minute_shape: Option<ShapeArg>,
+
+    /// Second hand shape [default: line]
+    #[arg(long, global = true, value_enum, value_name = "SHAPE")]
+    pub second_shape: Option<ShapeArg>,
+
+    /// What marks the hours [default: dots]
+    #[arg(long, global = true, value_enum)]
+    pub face: Option<FaceArg>,
+
+    /// Symbol used to draw the hands [default: braille]
+    #[arg(long, global = true, value_enum)]
+    pub marker: Option<MarkerArg>,
+
+    /// Shortest time between redraws in milliseconds; only matters while
+    /// something moves continuously, e.g. with --sweep
+    #[arg(long, global = true, value_name = "MS", default_value_t = 20,
+          value_parser = clap::value_parser!(u64).range(1..=1000))]
+    pub refresh: u64,
+
+    /// Sweep the hands continuously instead of ticking once a second
+    #[arg(long, global = true)]
+    pub sweep: bool,
+
+    /// Show how often the screen is redrawn and the loop wakes up
+    #[arg(long, global = true)]
+    pub frames: bool,
+
+    /// Run the clock FACTOR times as fast, e.g. 60 for a minute per second
+    #[arg(long, global = true, value_name = "FACTOR", value_parser = parse_speed)]
+    pub speed: Option<f64>,
+
+    /// Shift the clock, e.g. 2h or -30m
+    #[arg(long, global = true, value_name = "DURATION", allow_hyphen_values = true,
+          value_parser = parse_offset)]
+    pub offset: Option<time::Duration>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Show the clock (the default)
+    Clock,
+    /// Count down from DURATION, e.g. 25m, 1h30m, 90s (space pause, + add a minute).
+    /// Exits 0 when it reaches zero and 1 when cancelled
+    Timer {
+        #[arg(value_parser = parse_duration)]
+        duration: Duration,
+    },
+    /// Pomodoro cycles: work, short breaks and a long break every ROUNDS work
+    /// periods (space pause, n skip). Completed phases go to the history file
+    Pomodoro {
+        /// Length of a work period
+        #[arg(long, default_value = "25m", value_parser = parse_duration)]
+        work: Duration,
+        /// Break between work periods
+        #[arg(long, default_value = "5m", value_parser = parse_duration)]
+        short_break: Duration,
+        /// Break after the last round
+        #[arg(long, default_value = "15m", value_parser = parse_duration)]
+        long_break: Duration,
+        /// Work periods per cycle
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+        rounds: u16,
+    },
+    /// Interval training, e.g. 8x20s/10s for eight rounds of 20s on and 10s off.
+    /// Exits 0 after the last round and 1 when cancelled
+    Interval {
+        #[arg(value_name = "ROUNDSxON/OFF", value_parser = parse_interval)]
+        program: Program,
+    },
+    /// Print daily totals of completed pomodoro and interval phases
+    History,
+    /// Manage alarms; they go off in any running tock (z snooze, x dismiss)
+    Alarm {
+        #[command(subcommand)]
+        action: AlarmAction,
+    },
+    /// Measure elapsed time with laps (space start/stop, l lap, r reset)
+    Stopwatch {
+        /// Write the laps to PATH on exit
+        #[arg(long, value_name = "PATH")]
+        export: Option<PathBuf>,
+        /// Export format [default: from the file extension, else csv]
+        #[arg(long, value_enum, requires = "export")]
+        format: Option<ExportFormat>,
+    },
+    /// Show several time zones side by side
+    World {
+        /// Zones to show, e.g. Europe/Berlin America/New_York Asia/Tokyo
+        /// [default: `world` from the config, else the clock's zone]
+        #[arg(value_parser = Zone::parse)]
+        zones: Vec<Zone>,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AlarmAction {
+    /// Add an alarm, e.g. `07:30 weekdays Stand-up` or `22:00 Bed`
+    Add {
+        /// HH:MM [once|daily|weekdays|weekends|every mon,wed,...] [LABEL]
+        #[arg(required = true, value_name = "ALARM")]
+        words: Vec<String>,
+    },
+    /// List the alarms with their numbers
+    List,
+    /// Remove alarm NUMBER as shown by `tock alarm list`
+    Remove { number: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub width: u16,
+    pub height: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkerArg {
+    Dot,
+    Braille,
+    Block,
+}
+
+impl From<MarkerArg> for Marker {
+    fn from(value: MarkerArg) -> Self {
+        match value {
+            MarkerArg::Dot => Marker::Dot,
+            MarkerArg::Braille => Marker::Braille,
+            MarkerArg::Block => Marker::Block,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorsArg {
+    #[value(name = "16")]
+    Ansi16,
+    #[value(name = "256")]
+    Ansi256,
+    Truecolor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FaceArg {
+    Dots,
+    Arabic,
+    Roman,
+    Ticks,
+    #[value(name = "24h")]
+    #[serde(rename = "24h")]
+    TwentyFour,
+    Plain,
+}
+
+impl From<FaceArg> for Face {
+    fn from(value: FaceArg) -> Self {
+        match value {
+            FaceArg::Dots => Face::Dots,
+            FaceArg::Arabic => Face::Arabic,
+            FaceArg::Roman => Face::Roman,
+            FaceArg::Ticks => Face::Ticks,
+            FaceArg::TwentyFour => Face::TwentyFour,
+            FaceArg::Plain => Face::Plain,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShapeArg {
+    Line,
+    Tapered,
+    Arrow,
+    Counterweight,
+}
+
+impl From<ShapeArg> for HandShape {
+    fn from(value: ShapeArg) -> Self {
+        match value {
+            ShapeArg::Line => HandShape::Line,
+            ShapeArg::Tapered => HandShape::Tapered,
+            ShapeArg::Arrow => HandShape::Arrow,
+            ShapeArg::Counterweight => HandShape::Counterweight,
+        }
+    }
+}
+
+impl Cli {
+    /// `base` with every style flag given on the command line applied.
+    pub fn style(&self, base: DialStyle) -> DialStyle {
+        DialStyle {
+            hour: self.hour_color.unwrap_or(base.hour),
+            minute: self.minute_color.unwrap_or(base.minute),
+            second: self.second_color.unwrap_or(base.second),
+            marks: self.mark_color.unwrap_or(base.marks),
+            face: self.face.map(Face::from).unwrap_or(base.face),
+            hour_shape: self
+                .hour_shape
+                .map(HandShape::from)
+                .unwrap_or(base.hour_shape),
+            minute_shape: self
+                .minute_shape
+                .map(HandShape::from)
+                .unwrap_or(base.minute_shape),
+            second_shape: self
+                .second_shape
+                .map(HandShape::from)
+                .unwrap_or(base.second_shape),
+            marker: self.marker.map(Marker::from).unwrap_or(base.marker),
+            ..base
+        }
+    }
+
+    pub fn color_depth(&self) -> ColorDepth {
+        match self.colors {
+            Some(ColorsArg::Ansi16) => ColorDepth::Ansi16,
+            Some(ColorsArg::Ansi256) => ColorDepth::Ansi256,
+            Some(ColorsArg::Truecolor) => ColorDepth::TrueColor,
+            None => ColorDepth::detect(),
+        }
+    }
+
+    /// The real clock, unless `--speed` or `--offset` asked for another.
+    pub fn time_source(&self) -> Box<dyn TimeSource> {
+        if self.speed.is_none() && self.offset.is_none() {
+            return Box::new(SystemClock);
+        }
+        Box::new(ScaledClock::new(
+            SystemClock,
+            self.offset.unwrap_or(time::Duration::ZERO),
+            self.speed.unwrap_or(1.0),
+        ))
+    }
+
+    pub fn motion(&self) -> Motion {
+        if self.sweep {
+            Motion::Sweep
+        } else {
+            Motion::Tick
+        }
+    }
+}
+
+/// `80x40`
+pub fn parse_size(s: &str) -> Result<Size, String> {
+    let (w, h) = s
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got '{}'", s))?;
+    let width = w.trim().parse().map_err(|_| format!("bad width '{}'", w))?;
+    let height = h
+        .trim()
+        .parse()
+        .map_err(|_| format!("bad height '{}'", h))?;
+    Ok(Size { width, height })
+}
+
+/// Longest duration `parse_duration` accepts.
+pub const MAX_DURATION: Duration = Duration::from_secs(1000 * 3600);
+/// Furthest `--offset` shifts the clock either way, about a century.
+const MAX_OFFSET: Duration = Duration::from_secs(876_000 * 3600);
+/// Fastest `--speed`: a day in under a second.
+const MAX_SPEED: f64 = 100_000.0;
+
+/// `25m`, `1h30m`, `90s`, `1h2m3s`; a bare number counts as minutes.
+/// At most `MAX_DURATION`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let duration = parse_duration_up_to(s, MAX_DURATION)?;
+    if duration.is_zero() {
+        return Err("duration must be longer than zero".to_string());
+    }
+    Ok(duration)
+}
+
+fn parse_duration_up_to(s: &str, max: Duration) -> Result<Duration, String> {
+    let s = s.trim();
+    let too_long = || {
+        let max = max.as_secs() / 3600;
+        format!("duration '{}' is too long (at most {}h)", s, max)
+    };
+    if s.is_empty() {
+        return Err("expected a duration like 25m, 1h30m or 90s".to_string());
+    }
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        let secs = s
+            .parse::<u64>()
+            .ok()
+            .and_then(|minutes| minutes.checked_mul(60))
+            .ok_or_else(too_long)?;
+        return check_duration(secs, max, too_long);
+    }
+    let mut total = 0u64;
+    let mut num = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            num.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("unknown unit '{}' in '{}' (use h, m or s)", c, s)),
+        };
+        if num.is_empty() {
+            return Err(format!("missing number before '{}' in '{}'", c, s));
+        }
+        total = num
+            .parse::<u64>()
+            .ok()
+            .and_then(|n| n.checked_mul(unit))
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(too_long)?;
+        num.clear();
+    }
+    if !num.is_empty() {
+        return Err(format!("missing unit after '{}' in '{}'", num, s));
+    }
+    check_duration(total, max, too_long)
+}
+
+fn check_duration(
+    secs: u64,
+    max: Duration,
+    too_long: impl Fn() -> String,
+) -> Result<Duration, String> {
+    let duration = Duration::from_secs(secs);
+    if duration > max {
+        return Err(too_long());
+    }
+    Ok(duration)
+}
+
+/// A `parse_duration` with an optional sign, up to `MAX_OFFSET`; zero is
+/// fine.
+pub fn parse_offset(s: &str) -> Result<time::Duration, String> {
+    let s = s.trim();
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let duration = parse_duration_up_to(rest, MAX_OFFSET)?;
+    let duration = time::Duration::try_from(duration).map_err(|e| e.to_string())?;
+    Ok(duration * sign)
+}
+
+/// A factor from 0 up to `MAX_SPEED`.
+pub fn parse_speed(s: &str) -> Result<f64, String> {
+    match s.trim().parse::<f64>() {
+        Ok(speed) if (0.0..=MAX_SPEED).contains(&speed) => Ok(speed),
+        _ => Err(format!(
+            "expected a factor from 0 to {}, got '{}'",
+            MAX_SPEED, s
+        )),
+    }
+}
+
+/// Colour names as tui spells them (`light-red`, `dark-gray`), `#rrggbb`
+/// or a 256 colour palette index.
+pub fn parse_color(s: &str) -> Result<Color, String> {
+    let name = s.trim().to_ascii_lowercase().replace(['-', '_', ' '], "");
+    let color = match name.as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => {
+            if let Some(hex) = name.strip_prefix('#') {
+                if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(format!("expected #rrggbb, got '{}'", s));
+                }
+                let rgb =
+                    u32::from_str_radix(hex, 16).map_err(|_| format!("bad hex colour '{}'", s))?;
+                Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+            } else if let Ok(index) = name.parse::<u8>() {
+                Color::Indexed(index)
+            } else {
+                return Err(format!("unknown colour '{}'", s));
+            }
+        }
+    };
+    Ok(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_zones_are_optional() {
+        let cli = Cli::try_parse_from(["tock", "world"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::World { zones }) if zones.is_empty()));
+        let cli = Cli::try_parse_from(["tock", "world", "UTC", "Asia/Tokyo"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::World { zones }) if zones.len() == 2));
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(parse_color("Light-Blue"), Ok(Color::LightBlue));
+        assert_eq!(parse_color("#FF8700"), Ok(Color::Rgb(0xff, 0x87, 0x00)));
+        assert_eq!(parse_color("208"), Ok(Color::Indexed(208)));
+        // from_str_radix would take the sign
+        for s in ["#+12345", "#-12345", "#12345", "#12345g"] {
+            assert!(parse_color(s).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("25"), Ok(Duration::from_secs(25 * 60)));
+        assert_eq!(parse_duration("1h2m3s"), Ok(Duration::from_secs(3723)));
+        assert_eq!(parse_duration("1000h"), Ok(MAX_DURATION));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("h").unwrap_err().contains("missing number"));
+    }
+
+    #[test]
+    fn huge_durations_are_too_long() {
+        for s in [
+            "1000h1s",
+            "60001",
+            "5000000000000000h",
+            "99999999999999999999999",
+        ] {
+            let err = parse_duration(s).unwrap_err();
+            assert!(err.contains("too long"), "{}: {}", s, err);
+        }
+    }
+
+    #[test]
+    fn offsets_and_speeds_are_bounded() {
+        assert_eq!(parse_offset("-30m"), Ok(time::Duration::minutes(-30)));
+        assert_eq!(parse_offset("+2000h"), Ok(time::Duration::hours(2000)));
+        assert!(parse_offset("876000h").is_ok());
+        assert_eq!(parse_offset("0"), Ok(time::Duration::ZERO));
+        assert_eq!(parse_offset("-0s"), Ok(time::Duration::ZERO));
+        assert!(parse_offset("-").is_err());
+        let err = parse_offset("1000000000h").unwrap_err();
+        assert!(err.contains("too long"), "{}", err);
+
+        assert_eq!(parse_speed("0"), Ok(0.0));
+        assert_eq!(parse_speed("2.5"), Ok(2.5));
+        for s in ["1e30", "inf", "NaN", "-1"] {
+            assert!(parse_speed(s).is_err(), "{}", s);
+        }
+    }
+}
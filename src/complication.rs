@@ -0,0 +1,189 @@
+// ====================================
+// ===== Complications
+// ====================================
+
+use std::fmt;
+
+use time::OffsetDateTime;
+use tui::{buffer::Buffer, layout::Rect, style::Style};
+
+use crate::dial::label;
+
+/// A small readout drawn inside the dial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complication {
+    /// `10:08:37`
+    Digital,
+    /// `Mar 10`
+    Date,
+    /// `Sun`
+    Weekday,
+    /// `W10`, the ISO week number.
+    Week,
+    /// `AM` or `PM`.
+    AmPm,
+}
+
+impl Complication {
+    /// In the order they are joined when sharing a slot.
+    pub const ALL: [Complication; 5] = [
+        Complication::Digital,
+        Complication::Date,
+        Complication::Weekday,
+        Complication::Week,
+        Complication::AmPm,
+    ];
+
+    /// The readout for `time`, already in the dial's zone.
+    pub fn text(self, time: OffsetDateTime) -> String {
+        match self {
+            Complication::Digital => {
+                format!(
+                    "{:02}:{:02}:{:02}",
+                    time.hour(),
+                    time.minute(),
+                    time.second()
+                )
+            }
+            Complication::Date => {
+                format!("{} {}", &time.month().to_string()[..3], time.day())
+            }
+            Complication::Weekday => time.weekday().to_string()[..3].to_string(),
+            Complication::Week => format!("W{:02}", time.iso_week()),
+            Complication::AmPm => if time.hour() < 12 { "AM" } else { "PM" }.to_string(),
+        }
+    }
+
+    /// Where it goes when nothing else is said.
+    pub fn default_slot(self) -> Slot {
+        match self {
+            Complication::Digital => Slot::Bottom,
+            Complication::Date => Slot::Right,
+            Complication::Weekday => Slot::Left,
+            Complication::Week | Complication::AmPm => Slot::Top,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// One of the four sub-areas between the centre and the hour marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Slot {
+    pub const ALL: [Slot; 4] = [Slot::Top, Slot::Bottom, Slot::Left, Slot::Right];
+
+    /// `top`, `bottom`, `left` or `right`.
+    pub fn parse(s: &str) -> Result<Slot, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "top" => Ok(Slot::Top),
+            "bottom" => Ok(Slot::Bottom),
+            "left" => Ok(Slot::Left),
+            "right" => Ok(Slot::Right),
+            _ => Err(format!(
+                "unknown position '{}', expected top, bottom, left or right",
+                s
+            )),
+        }
+    }
+
+    /// Centre of the slot in dial coordinates.
+    fn anchor(self) -> (f64, f64) {
+        match self {
+            Slot::Top => (0.0, 0.4),
+            Slot::Bottom => (0.0, -0.4),
+            Slot::Left => (-0.45, 0.0),
+            Slot::Right => (0.45, 0.0),
+        }
+    }
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Slot::Top => "top",
+            Slot::Bottom => "bottom",
+            Slot::Left => "left",
+            Slot::Right => "right",
+        })
+    }
+}
+
+/// Where each complication sits and whether it is shown. Hidden ones keep
+/// their slot, so toggling one back on puts it where it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Complications {
+    slots: [Slot; 5],
+    shown: [bool; 5],
+}
+
+impl Complications {
+    /// All hidden, each in its default slot.
+    pub fn new() -> Complications {
+        Complications {
+            slots: Complication::ALL.map(Complication::default_slot),
+            shown: [false; 5],
+        }
+    }
+
+    /// Shows `complication` in `slot`.
+    pub fn show(mut self, complication: Complication, slot: Slot) -> Complications {
+        self.slots[complication.index()] = slot;
+        self.shown[complication.index()] = true;
+        self
+    }
+
+    pub fn is_shown(&self, complication: Complication) -> bool {
+        self.shown[complication.index()]
+    }
+
+    pub fn slot(&self, complication: Complication) -> Slot {
+        self.slots[complication.index()]
+    }
+
+    pub fn toggle(&mut self, complication: Complication) {
+        self.shown[complication.index()] ^= true;
+    }
+
+    /// Draws the shown complications for `time` into a dial drawn in `area`.
+    /// Readouts that do not fit are left out.
+    pub fn draw(&self, area: Rect, buf: &mut Buffer, time: OffsetDateTime, style: Style) {
+        for slot in Slot::ALL {
+            let text = Complication::ALL
+                .into_iter()
+                .filter(|&c| self.is_shown(c) && self.slot(c) == slot)
+                .map(|c| c.text(time))
+                .collect::<Vec<_>>()
+                .join(" ");
+            label(area, buf, slot.anchor(), &text, style);
+        }
+    }
+}
+
+impl Default for Complications {
+    fn default() -> Self {
+        Complications::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn readouts() {
+        let time = datetime!(2024-12-30 21:05:09 UTC);
+        let texts = Complication::ALL.map(|c| c.text(time));
+        // the last days of December can belong to week 1 of the next year
+        assert_eq!(texts, ["21:05:09", "Dec 30", "Mon", "W01", "PM"]);
+    }
+}
@@ -0,0 +1,479 @@
+// ====================================
+// ===== Config file
+// ====================================
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::{Deserialize, Deserializer};
+use tui::{style::Color, symbols::Marker};
+
+use crate::cli::{parse_color, parse_duration, FaceArg, MarkerArg, ShapeArg};
+use crate::keys::Keys;
+use tock::complication::{Complication, Complications, Slot};
+use tock::dial::DialStyle;
+use tock::theme::Theme;
+use tock::zone::Zone;
+
+/// Everything `~/.config/tock/config.toml` can set. Missing keys fall back
+/// to the built-in defaults, command line flags win over the file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(deserialize_with = "de_zone")]
+    pub zone: Option<Zone>,
+    /// Zones for `tock world` when none are given.
+    #[serde(deserialize_with = "de_zones")]
+    pub world: Vec<Zone>,
+    /// A built-in theme or one of `themes`.
+    pub theme: Option<String>,
+    pub themes: BTreeMap<String, ThemeConfig>,
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+    pub hands: HandsConfig,
+    pub marks: MarksConfig,
+    pub alarm: AlarmConfig,
+    pub complications: ComplicationsConfig,
+    pub keys: Keys,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HandsConfig {
+    pub hour_length: f64,
+    pub minute_length: f64,
+    pub second_length: f64,
+    pub hour_width: f64,
+    pub minute_width: f64,
+    pub second_width: f64,
+    #[serde(deserialize_with = "de_color")]
+    pub hour_color: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub minute_color: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub second_color: Option<Color>,
+    pub marker: MarkerArg,
+    pub hour_shape: ShapeArg,
+    pub minute_shape: ShapeArg,
+    pub second_shape: ShapeArg,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarksConfig {
+    pub face: FaceArg,
+    pub style: MarkStyle,
+    #[serde(deserialize_with = "de_color")]
+    pub color: Option<Color>,
+}
+
+/// A theme of its own: `base` (default `dark`) with some colours replaced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub base: Option<String>,
+    #[serde(deserialize_with = "de_color")]
+    pub hour: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub minute: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub second: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub marks: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub marks_to: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub arc: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub arc_to: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub work: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub short_break: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub long_break: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub accent: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub error: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub notice: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub dim: Option<Color>,
+    #[serde(deserialize_with = "de_color")]
+    pub flash: Option<Color>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlarmConfig {
+    /// Shell command run whenever an alarm goes off.
+    pub command: Option<String>,
+    #[serde(deserialize_with = "de_duration")]
+    pub snooze: Duration,
+}
+
+/// Position of each complication, `None` for `"off"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComplicationsConfig {
+    #[serde(deserialize_with = "de_slot")]
+    pub digital: Option<Slot>,
+    #[serde(deserialize_with = "de_slot")]
+    pub date: Option<Slot>,
+    #[serde(deserialize_with = "de_slot")]
+    pub weekday: Option<Slot>,
+    #[serde(deserialize_with = "de_slot")]
+    pub week: Option<Slot>,
+    #[serde(deserialize_with = "de_slot")]
+    pub am_pm: Option<Slot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkStyle {
+    None,
+    Dot,
+    Braille,
+    Block,
+}
+
+impl Default for HandsConfig {
+    fn default() -> Self {
+        let style = DialStyle::default();
+        HandsConfig {
+            hour_length: style.hour_length,
+            minute_length: style.minute_length,
+            second_length: style.second_length,
+            hour_width: style.hour_width,
+            minute_width: style.minute_width,
+            second_width: style.second_width,
+            hour_color: None,
+            minute_color: None,
+            second_color: None,
+            marker: MarkerArg::Braille,
+            hour_shape: ShapeArg::Line,
+            minute_shape: ShapeArg::Line,
+            second_shape: ShapeArg::Line,
+        }
+    }
+}
+
+impl Default for MarksConfig {
+    fn default() -> Self {
+        MarksConfig {
+            face: FaceArg::Dots,
+            style: MarkStyle::Dot,
+            color: None,
+        }
+    }
+}
+
+impl Default for AlarmConfig {
+    fn default() -> Self {
+        AlarmConfig {
+            command: None,
+            snooze: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl ThemeConfig {
+    fn apply(&self, base: Theme) -> Theme {
+        Theme {
+            hour: self.hour.unwrap_or(base.hour),
+            minute: self.minute.unwrap_or(base.minute),
+            second: self.second.unwrap_or(base.second),
+            marks: self.marks.unwrap_or(base.marks),
+            marks_to: self.marks_to.or(base.marks_to),
+            arc: self.arc.unwrap_or(base.arc),
+            arc_to: self.arc_to.or(base.arc_to),
+            work: self.work.unwrap_or(base.work),
+            short_break: self.short_break.unwrap_or(base.short_break),
+            long_break: self.long_break.unwrap_or(base.long_break),
+            accent: self.accent.unwrap_or(base.accent),
+            error: self.error.unwrap_or(base.error),
+            notice: self.notice.unwrap_or(base.notice),
+            dim: self.dim.unwrap_or(base.dim),
+            flash: self.flash.unwrap_or(base.flash),
+        }
+    }
+}
+
+impl ComplicationsConfig {
+    pub fn complications(&self) -> Complications {
+        [
+            (Complication::Digital, self.digital),
+            (Complication::Date, self.date),
+            (Complication::Weekday, self.weekday),
+            (Complication::Week, self.week),
+            (Complication::AmPm, self.am_pm),
+        ]
+        .into_iter()
+        .fold(
+            Complications::new(),
+            |all, (complication, slot)| match slot {
+                Some(slot) => all.show(complication, slot),
+                None => all,
+            },
+        )
+    }
+}
+
+impl MarkStyle {
+    pub fn marker(self) -> Option<Marker> {
+        match self {
+            MarkStyle::None => None,
+            MarkStyle::Dot => Some(Marker::Dot),
+            MarkStyle::Braille => Some(Marker::Braille),
+            MarkStyle::Block => Some(Marker::Block),
+        }
+    }
+}
+
+impl Config {
+    /// Parses the config at `path`; a missing file is the default config.
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let config: Config =
+            toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        config
+            .validate()
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("hour_length", self.hands.hour_length),
+            ("minute_length", self.hands.minute_length),
+            ("second_length", self.hands.second_length),
+            ("hour_width", self.hands.hour_width),
+            ("minute_width", self.hands.minute_width),
+            ("second_width", self.hands.second_width),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!(
+                    "hands.{} must be between 0.0 and 1.0, got {}",
+                    name, value
+                ));
+            }
+        }
+        self.theme(None)?;
+        self.keys.validate()
+    }
+
+    /// The theme called `name`, else the one the file picks, else `dark`.
+    pub fn theme(&self, name: Option<&str>) -> Result<Theme, String> {
+        let name = name.or(self.theme.as_deref()).unwrap_or("dark");
+        if let Some(custom) = self.themes.get(name) {
+            let base = custom.base.as_deref().unwrap_or("dark");
+            let base = Theme::builtin(base).ok_or_else(|| {
+                format!(
+                    "themes.{}: unknown base '{}', expected {}",
+                    name,
+                    base,
+                    Theme::NAMES.join(", ")
+                )
+            })?;
+            return Ok(custom.apply(base));
+        }
+        Theme::builtin(name).ok_or_else(|| {
+            format!(
+                "unknown theme '{}', expected {} or one from [themes]",
+                name,
+                Theme::NAMES.join(", ")
+            )
+        })
+    }
+
+    /// The dial of `theme` with the colours the file sets.
+    pub fn style(&self, theme: &Theme) -> DialStyle {
+        let base = theme.style(DialStyle::default());
+        DialStyle {
+            hour: self.hands.hour_color.unwrap_or(base.hour),
+            minute: self.hands.minute_color.unwrap_or(base.minute),
+            second: self.hands.second_color.unwrap_or(base.second),
+            marks: self.marks.color.unwrap_or(base.marks),
+            marks_to: base.marks_to,
+            face: self.marks.face.into(),
+            marker: self.hands.marker.into(),
+            mark_marker: self.marks.style.marker(),
+            hour_length: self.hands.hour_length,
+            minute_length: self.hands.minute_length,
+            second_length: self.hands.second_length,
+            hour_width: self.hands.hour_width,
+            minute_width: self.hands.minute_width,
+            second_width: self.hands.second_width,
+            hour_shape: self.hands.hour_shape.into(),
+            minute_shape: self.hands.minute_shape.into(),
+            second_shape: self.hands.second_shape.into(),
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/tock/config.toml`, else `~/.config/tock/config.toml`.
+pub fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("tock").join("config.toml"))
+}
+
+/// `$XDG_DATA_HOME/tock`, else `~/.local/share/tock`; holds the history and
+/// the alarms.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+        })?;
+    Some(base.join("tock"))
+}
+
+/// Notices when the config file changes on disk by polling its mtime.
+pub struct Watcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    checked: Instant,
+}
+
+impl Watcher {
+    const INTERVAL: Duration = Duration::from_millis(500);
+
+    pub fn new(path: PathBuf) -> Watcher {
+        let modified = modified(&path);
+        Watcher {
+            path,
+            modified,
+            checked: Instant::now(),
+        }
+    }
+
+    /// The freshly parsed config if the file changed since the last call.
+    pub fn poll(&mut self) -> Option<Result<Config, String>> {
+        if self.checked.elapsed() < Self::INTERVAL {
+            return None;
+        }
+        self.checked = Instant::now();
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(Config::load(&self.path))
+    }
+}
+
+pub fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn de_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_color(&s).map(Some).map_err(serde::de::Error::custom)
+}
+
+fn de_duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_duration(&s).map_err(serde::de::Error::custom)
+}
+
+fn de_zone<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Zone>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    Zone::parse(&s).map(Some).map_err(serde::de::Error::custom)
+}
+
+fn de_zones<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Zone>, D::Error> {
+    let names = Vec::<String>::deserialize(deserializer)?;
+    names
+        .iter()
+        .map(|name| Zone::parse(name).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn de_slot<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Slot>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    if s.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    Slot::parse(&s).map(Some).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_themes_start_from_a_base() {
+        let config: Config = toml::from_str(
+            r##"
+            theme = "dusk"
+            [themes.dusk]
+            base = "solarized"
+            hour = "#ff8700"
+            "##,
+        )
+        .unwrap();
+        let theme = config.theme(None).unwrap();
+        assert_eq!(theme.hour, Color::Rgb(0xff, 0x87, 0x00));
+        assert_eq!(theme.minute, Theme::SOLARIZED.minute);
+        // the command line wins over the file
+        assert_eq!(config.theme(Some("light")), Ok(Theme::LIGHT));
+        assert!(config.theme(Some("neon")).is_err());
+    }
+
+    #[test]
+    fn colours_in_the_file_win_over_the_theme() {
+        let mut config = Config::default();
+        config.hands.minute_color = Some(Color::Magenta);
+        let style = config.style(&Theme::HIGH_CONTRAST);
+        assert_eq!(style.hour, Color::LightRed);
+        assert_eq!(style.minute, Color::Magenta);
+    }
+
+    #[test]
+    fn hand_widths() {
+        let style = Config::default().style(&Theme::DARK);
+        assert_eq!(
+            (style.hour_width, style.minute_width, style.second_width),
+            (0.12, 0.09, 0.06)
+        );
+        let config: Config = toml::from_str("[hands]\nhour_width = 0.2").unwrap();
+        assert_eq!(config.style(&Theme::DARK).hour_width, 0.2);
+        let config: Config = toml::from_str("[hands]\nsecond_width = 1.5").unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn world_zones() {
+        let config: Config = toml::from_str("world = [\"UTC\", \"Asia/Tokyo\"]").unwrap();
+        let names: Vec<String> = config.world.iter().map(|zone| zone.name()).collect();
+        assert_eq!(names, ["UTC", "Asia/Tokyo"]);
+        assert!(toml::from_str::<Config>("world = [\"Mars/Base\"]").is_err());
+    }
+
+    #[test]
+    fn keys_must_not_clash() {
+        let config: Config = toml::from_str("[keys]\nhelp = \"h\"\nquit = \"esc\"").unwrap();
+        assert_eq!(config.validate(), Ok(()));
+        let config: Config = toml::from_str("[keys]\nface = \"s\"").unwrap();
+        assert_eq!(
+            config.validate(),
+            Err("keys.sweep and keys.face are both 's'".to_string())
+        );
+        // mode keys clash with the general ones too
+        let config: Config = toml::from_str("[keys]\nlap = \"t\"").unwrap();
+        assert!(config.validate().is_err());
+    }
+}
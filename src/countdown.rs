@@ -0,0 +1,154 @@
+// ====================================
+// ===== Countdown timer
+// ====================================
+
+use std::time::{Duration, Instant};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    widgets::{Block, Borders, GraphType, Paragraph},
+    Frame,
+};
+
+use crate::cli::MAX_DURATION;
+use crate::stopwatch::format_duration;
+use tock::dial::{dial_rot, Dial, Hands, Layer, Motion};
+use tock::theme::Theme;
+use tock::ClockWidget;
+
+/// Counts down to zero; pausing keeps the remaining time, resuming moves
+/// the deadline.
+#[derive(Debug, Clone)]
+pub struct Countdown {
+    deadline: Option<Instant>,
+    paused_remaining: Duration,
+}
+
+impl Countdown {
+    /// A running countdown of `duration` starting at `now`, cut to
+    /// `MAX_DURATION`.
+    pub fn new(duration: Duration, now: Instant) -> Countdown {
+        let duration = duration.min(MAX_DURATION);
+        Countdown {
+            deadline: Some(now + duration),
+            paused_remaining: duration,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    pub fn remaining(&self, now: Instant) -> Duration {
+        match self.deadline {
+            Some(deadline) => deadline.saturating_duration_since(now),
+            None => self.paused_remaining,
+        }
+    }
+
+    pub fn is_done(&self, now: Instant) -> bool {
+        self.remaining(now).is_zero()
+    }
+
+    pub fn toggle(&mut self, now: Instant) {
+        match self.deadline.take() {
+            Some(deadline) => self.paused_remaining = deadline.saturating_duration_since(now),
+            None => self.deadline = Some(now + self.paused_remaining),
+        }
+    }
+
+    /// Adds `extra`, up to `MAX_DURATION` left.
+    pub fn add(&mut self, extra: Duration, now: Instant) {
+        let remaining = self.remaining(now).saturating_add(extra).min(MAX_DURATION);
+        match &mut self.deadline {
+            Some(deadline) => *deadline = now + remaining,
+            None => self.paused_remaining = remaining,
+        }
+    }
+
+    pub fn render<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        dial: &Dial,
+        now: Instant,
+        motion: Motion,
+        theme: &Theme,
+    ) {
+        let remaining = self.remaining(now);
+        let state = if self.is_running() { "" } else { " (paused)" };
+        let title = format!("Timer{}", state);
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+
+        // ticking hands and arc round up, so they reach zero as the timer rings
+        let seconds = remaining.as_secs_f64();
+        let shown = match motion {
+            Motion::Tick => seconds.ceil(),
+            Motion::Sweep => seconds,
+        };
+        let layers = arc_layers(shown / 3600.0, theme);
+        let clock = ClockWidget::new()
+            .dial(dial)
+            .hands(Hands::from_seconds(shown))
+            .layers(&layers);
+        f.render_widget(clock, parts[0]);
+
+        let readout = Paragraph::new(format_duration(remaining))
+            .alignment(Alignment::Center)
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        f.render_widget(readout, parts[1]);
+    }
+}
+
+/// A filled sector from 12 o'clock clockwise over `turns` of the dial
+/// (capped at a full circle), drawn as a fan of radial lines.
+pub fn arc_layer(turns: f64, color: Color) -> Layer {
+    sector(0.0, turns.clamp(0.0, 1.0), color)
+}
+
+/// `arc_layer` in the arc colour of `theme`, in slices that shade along
+/// the way when the theme has a gradient.
+pub fn arc_layers(turns: f64, theme: &Theme) -> Vec<Layer> {
+    const SLICES: usize = 24;
+    let turns = turns.clamp(0.0, 1.0);
+    if theme.arc_to.is_none() {
+        return vec![arc_layer(turns, theme.arc)];
+    }
+    (0..SLICES)
+        .map(|i| (i as f64 / SLICES as f64, (i + 1) as f64 / SLICES as f64))
+        .take_while(|&(from, _)| from < turns)
+        .map(|(from, to)| sector(from, to.min(turns), theme.arc_at((from + to) / 2.0)))
+        .collect()
+}
+
+fn sector(from: f64, to: f64, color: Color) -> Layer {
+    const STEPS: usize = 480;
+    const RADIUS: f64 = 0.85;
+    let center = (0.0, 0.0);
+    let steps = ((to - from) * STEPS as f64).ceil() as usize;
+    let mut points = Vec::with_capacity(steps * 2 + 1);
+    if steps > 0 {
+        points.push(center);
+        for i in 0..=steps {
+            let turn = (from + i as f64 / STEPS as f64).min(to);
+            points.push((dial_rot(turn) * RADIUS).into());
+            points.push(center);
+        }
+    }
+    Layer {
+        points,
+        graph: GraphType::Line,
+        marker: Marker::Braille,
+        color,
+    }
+}
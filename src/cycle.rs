@@ -0,0 +1,322 @@
+// ====================================
+// ===== Pomodoro / interval cycles
+// ====================================
+
+use std::time::{Duration, Instant};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    widgets::{Block, Borders, GraphType, Paragraph},
+    Frame,
+};
+
+use crate::cli::parse_duration;
+use crate::countdown::{arc_layer, Countdown};
+use crate::stopwatch::format_duration;
+use tock::dial::{dial_rot, Dial, HandSet, Layer};
+use tock::theme::Theme;
+use tock::ClockWidget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseKind {
+    Work,
+    ShortBreak,
+    LongBreak,
+    On,
+    Off,
+}
+
+impl PhaseKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            PhaseKind::Work => "work",
+            PhaseKind::ShortBreak => "short-break",
+            PhaseKind::LongBreak => "long-break",
+            PhaseKind::On => "on",
+            PhaseKind::Off => "off",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<PhaseKind> {
+        [
+            PhaseKind::Work,
+            PhaseKind::ShortBreak,
+            PhaseKind::LongBreak,
+            PhaseKind::On,
+            PhaseKind::Off,
+        ]
+        .into_iter()
+        .find(|kind| kind.name() == s)
+    }
+
+    /// Phases that count towards the daily focus total.
+    pub fn is_active(self) -> bool {
+        matches!(self, PhaseKind::Work | PhaseKind::On)
+    }
+
+    pub fn color(self, theme: &Theme) -> Color {
+        match self {
+            PhaseKind::Work | PhaseKind::On => theme.work,
+            PhaseKind::ShortBreak | PhaseKind::Off => theme.short_break,
+            PhaseKind::LongBreak => theme.long_break,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Phase {
+    pub kind: PhaseKind,
+    pub duration: Duration,
+    /// 1-based round this phase belongs to.
+    pub round: usize,
+}
+
+/// A sequence of phases, optionally repeated forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub name: String,
+    pub phases: Vec<Phase>,
+    pub rounds: usize,
+    pub repeat: bool,
+}
+
+impl Program {
+    /// `rounds` work periods separated by short breaks, then a long break;
+    /// starts over afterwards.
+    pub fn pomodoro(work: Duration, short: Duration, long: Duration, rounds: usize) -> Program {
+        let mut phases = Vec::new();
+        for round in 1..=rounds {
+            phases.push(Phase {
+                kind: PhaseKind::Work,
+                duration: work,
+                round,
+            });
+            let (kind, duration) = if round == rounds {
+                (PhaseKind::LongBreak, long)
+            } else {
+                (PhaseKind::ShortBreak, short)
+            };
+            phases.push(Phase {
+                kind,
+                duration,
+                round,
+            });
+        }
+        Program {
+            name: "pomodoro".to_string(),
+            phases,
+            rounds,
+            repeat: true,
+        }
+    }
+
+    /// `rounds` x (`on`, `off`), run once; a zero `off` is left out.
+    pub fn interval(rounds: usize, on: Duration, off: Duration) -> Program {
+        let mut phases = Vec::new();
+        for round in 1..=rounds {
+            phases.push(Phase {
+                kind: PhaseKind::On,
+                duration: on,
+                round,
+            });
+            if !off.is_zero() {
+                phases.push(Phase {
+                    kind: PhaseKind::Off,
+                    duration: off,
+                    round,
+                });
+            }
+        }
+        Program {
+            name: "interval".to_string(),
+            phases,
+            rounds,
+            repeat: false,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|phase| phase.duration).sum()
+    }
+}
+
+/// `8x20s/10s` (eight rounds of 20s on, 10s off) or `5x1m` (no rest).
+pub fn parse_interval(s: &str) -> Result<Program, String> {
+    let (rounds, rest) = s
+        .split_once(['x', 'X', '*'])
+        .ok_or_else(|| format!("expected ROUNDSxON/OFF like 8x20s/10s, got '{}'", s))?;
+    let rounds: usize = rounds
+        .trim()
+        .parse()
+        .map_err(|_| format!("bad round count '{}'", rounds))?;
+    if rounds == 0 {
+        return Err("an interval program needs at least one round".to_string());
+    }
+    let (on, off) = match rest.split_once('/') {
+        Some((on, off)) => (parse_duration(on)?, parse_duration(off)?),
+        None => (parse_duration(rest)?, Duration::ZERO),
+    };
+    Ok(Program::interval(rounds, on, off))
+}
+
+/// Runs a `Program`, one countdown per phase.
+#[derive(Debug, Clone)]
+pub struct Cycle {
+    program: Program,
+    index: usize,
+    countdown: Countdown,
+    finished: bool,
+}
+
+impl Cycle {
+    pub fn new(program: Program, now: Instant) -> Cycle {
+        let countdown = Countdown::new(program.phases[0].duration, now);
+        Cycle {
+            program,
+            index: 0,
+            countdown,
+            finished: false,
+        }
+    }
+
+    /// The countdown of the current phase.
+    pub fn countdown(&self) -> &Countdown {
+        &self.countdown
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.program.phases[self.index]
+    }
+
+    /// True once a non-repeating program ran through all its phases.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn toggle(&mut self, now: Instant) {
+        self.countdown.toggle(now);
+    }
+
+    /// Moves to the next phase if the current one ran out and returns the
+    /// phase that just completed.
+    pub fn tick(&mut self, now: Instant) -> Option<Phase> {
+        if self.finished || !self.countdown.is_done(now) {
+            return None;
+        }
+        let done = self.phase();
+        self.advance(now);
+        Some(done)
+    }
+
+    /// Abandons the current phase without counting it as completed.
+    pub fn skip(&mut self, now: Instant) {
+        if !self.finished {
+            self.advance(now);
+        }
+    }
+
+    fn advance(&mut self, now: Instant) {
+        self.index += 1;
+        if self.index == self.program.phases.len() {
+            if !self.program.repeat {
+                self.index -= 1;
+                self.finished = true;
+                return;
+            }
+            self.index = 0;
+        }
+        self.countdown = Countdown::new(self.phase().duration, now);
+    }
+
+    pub fn render<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        dial: &Dial,
+        now: Instant,
+        theme: &Theme,
+    ) {
+        let phase = self.phase();
+        let state = if self.countdown.is_running() {
+            ""
+        } else {
+            " (paused)"
+        };
+        let title = format!(
+            "{} - {} - round {}/{}{}",
+            self.program.name,
+            phase.kind.name(),
+            phase.round,
+            self.program.rounds,
+            state
+        );
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+
+        let remaining = self.countdown.remaining(now);
+        let mut layers = self.segments(theme);
+        let left = remaining.as_secs_f64() / phase.duration.as_secs_f64();
+        layers.push(arc_layer(left, phase.kind.color(theme)));
+        let clock = ClockWidget::new()
+            .dial(dial)
+            .hand_set(HandSet::NONE)
+            .layers(&layers);
+        f.render_widget(clock, parts[0]);
+
+        let readout = Paragraph::new(format_duration(remaining))
+            .alignment(Alignment::Center)
+            .style(
+                Style::default()
+                    .fg(phase.kind.color(theme))
+                    .add_modifier(Modifier::BOLD),
+            );
+        f.render_widget(readout, parts[1]);
+    }
+
+    /// The whole program as a ring of coloured segments; phases already
+    /// done in this cycle are dimmed.
+    fn segments(&self, theme: &Theme) -> Vec<Layer> {
+        const RADIUS: f64 = 0.95;
+        let total = self.program.total().as_secs_f64();
+        let mut start = 0.0;
+        let mut layers = Vec::new();
+        for (i, phase) in self.program.phases.iter().enumerate() {
+            let span = phase.duration.as_secs_f64() / total;
+            // leave a small gap between segments
+            let gap = (span * 0.1).min(0.005);
+            let steps = ((span * 360.0).ceil() as usize).max(2);
+            let points = (0..=steps)
+                .map(|j| {
+                    let turn = start + gap + (span - 2.0 * gap) * j as f64 / steps as f64;
+                    (dial_rot(turn) * RADIUS).into()
+                })
+                .collect();
+            let color = if i < self.index {
+                theme.dim
+            } else {
+                phase.kind.color(theme)
+            };
+            layers.push(Layer {
+                points,
+                graph: GraphType::Line,
+                marker: Marker::Braille,
+                color,
+            });
+            start += span;
+        }
+        layers
+    }
+}
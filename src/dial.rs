@@ -0,0 +1,480 @@
+// ====================================
+// ===== Dial geometry and drawing
+// ====================================
+
+use time::OffsetDateTime;
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::Marker,
+    widgets::{Axis, Block, Chart, Dataset, GraphType, Widget},
+};
+
+use vtx::Vtx2;
+
+use crate::hand::{fill, HandShape};
+use crate::theme::blend;
+
+/// Terminal cells are roughly twice as tall as they are wide.
+pub const CELL_ASPECT: f64 = 2.0;
+
+/// Axis bounds that keep a unit circle round when drawn into `area`.
+///
+/// Braille cells are 2x4 dots, so with `CELL_ASPECT` cells each dot is about
+/// square and the visible aspect is simply dots wide over dots high.
+pub fn bounds(area: Rect) -> ([f64; 2], [f64; 2]) {
+    if area.width == 0 || area.height == 0 {
+        return ([-1.0, 1.0], [-1.0, 1.0]);
+    }
+    let dots_w = area.width as f64 * 2.0;
+    let dots_h = area.height as f64 * 4.0 * (CELL_ASPECT / 2.0);
+    let aspect = dots_w / dots_h;
+    if aspect >= 1.0 {
+        ([-aspect, aspect], [-1.0, 1.0])
+    } else {
+        ([-1.0, 1.0], [-1.0 / aspect, 1.0 / aspect])
+    }
+}
+
+/// Width in cells that makes a `height` cells tall area square on screen.
+pub fn width_for(height: u16) -> u16 {
+    (height as f64 * CELL_ASPECT).round() as u16
+}
+
+/// Height in cells that makes a `width` cells wide area square on screen.
+pub fn height_for(width: u16) -> u16 {
+    (width as f64 / CELL_ASPECT).round() as u16
+}
+
+/// How far round from 12 o'clock `point` lies, the inverse of `dial_rot`.
+pub fn turn_of(point: (f64, f64)) -> f64 {
+    let turn = point.0.atan2(point.1) / (2.0 * std::f64::consts::PI);
+    turn.rem_euclid(1.0)
+}
+
+/// The cell `point` (in dial coordinates) falls into when a chart with
+/// `bounds(area)` is drawn into `area`, as fractional column and row.
+pub fn cell_at(area: Rect, point: (f64, f64)) -> (f64, f64) {
+    let (x_bounds, y_bounds) = bounds(area);
+    // the same braille dot grid the chart maps onto
+    let col = (point.0 - x_bounds[0]) / (x_bounds[1] - x_bounds[0])
+        * (area.width as f64 * 2.0 - 1.0)
+        / 2.0;
+    let row = (y_bounds[1] - point.1) / (y_bounds[1] - y_bounds[0])
+        * (area.height as f64 * 4.0 - 1.0)
+        / 4.0;
+    (col, row)
+}
+
+/// Writes `text` centred on `point`, shifted inwards to stay inside `area`.
+/// Text wider than `area` is left out.
+pub fn label(area: Rect, buf: &mut Buffer, point: (f64, f64), text: &str, style: Style) {
+    let width = text.chars().count() as u16;
+    if width == 0 || width > area.width || area.height == 0 {
+        return;
+    }
+    let (col, row) = cell_at(area, point);
+    let left = (col - (width - 1) as f64 / 2.0)
+        .round()
+        .clamp(0.0, (area.width - width) as f64) as u16;
+    let top = (row.floor().max(0.0) as u16).min(area.height - 1);
+    buf.set_string(area.x + left, area.y + top, text, style);
+}
+
+/// Unit vector pointing at `turn` (0.0 = 12 o'clock, 0.25 = 3 o'clock, ...).
+pub fn dial_rot(turn: f64) -> Vtx2 {
+    Vtx2::from_rot(-turn * (2.0 * std::f64::consts::PI) + (std::f64::consts::PI / 2.0))
+}
+
+/// How the hands advance between seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Motion {
+    /// Second hand jumps once per second, like a quartz movement.
+    #[default]
+    Tick,
+    /// Every hand moves continuously, down to the nanosecond.
+    Sweep,
+}
+
+impl Motion {
+    pub fn toggle(self) -> Motion {
+        match self {
+            Motion::Tick => Motion::Sweep,
+            Motion::Sweep => Motion::Tick,
+        }
+    }
+
+    /// Drops the sub-second part of `seconds` when ticking.
+    pub fn quantize(self, seconds: f64) -> f64 {
+        match self {
+            Motion::Tick => seconds.floor(),
+            Motion::Sweep => seconds,
+        }
+    }
+}
+
+/// Hand directions for one instant, as unit vectors in dial coordinates
+/// (centre at origin, 12 o'clock straight up).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hands {
+    pub hour: Vtx2,
+    pub minute: Vtx2,
+    pub second: Vtx2,
+}
+
+impl Hands {
+    /// Each hand carries the fraction of the smaller units, so at 3:30 the
+    /// hour hand sits halfway between the 3 and the 4.
+    pub fn at(time: OffsetDateTime, motion: Motion) -> Hands {
+        Hands::from_seconds(seconds_of_day(time, motion))
+    }
+
+    /// Hands showing `seconds` past 12 o'clock, or since a stopwatch started.
+    pub fn from_seconds(seconds: f64) -> Hands {
+        Hands::on_dial(seconds, 12)
+    }
+
+    /// Like `from_seconds` with the hour hand taking `hours` for a turn.
+    pub fn on_dial(seconds: f64, hours: u32) -> Hands {
+        Hands {
+            hour: dial_rot(seconds / (hours as f64 * 3600.0)),
+            minute: dial_rot(seconds / 3600.0),
+            second: dial_rot(seconds / 60.0),
+        }
+    }
+}
+
+fn seconds_of_day(time: OffsetDateTime, motion: Motion) -> f64 {
+    let (h, m, s, nano) = time.to_hms_nano();
+    let seconds =
+        (h as u32 * 3600 + m as u32 * 60 + s as u32) as f64 + nano as f64 / 1_000_000_000.0;
+    motion.quantize(seconds)
+}
+
+/// What marks the hours around the edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Face {
+    /// A dot at every hour.
+    #[default]
+    Dots,
+    /// 1 to 12.
+    Arabic,
+    /// I to XII.
+    Roman,
+    /// A tick for every minute, longer ones for the hours.
+    Ticks,
+    /// 0 to 22 in steps of two, the hour hand turning once a day.
+    TwentyFour,
+    /// Nothing but the hands.
+    Plain,
+}
+
+impl Face {
+    pub const ALL: [Face; 6] = [
+        Face::Dots,
+        Face::Arabic,
+        Face::Roman,
+        Face::Ticks,
+        Face::TwentyFour,
+        Face::Plain,
+    ];
+
+    /// The face after this one, wrapping around.
+    pub fn next(self) -> Face {
+        let i = Face::ALL.iter().position(|&face| face == self).unwrap_or(0);
+        Face::ALL[(i + 1) % Face::ALL.len()]
+    }
+
+    /// Hours for one turn of the hour hand.
+    pub fn hours(self) -> u32 {
+        match self {
+            Face::TwentyFour => 24,
+            _ => 12,
+        }
+    }
+}
+
+/// Which hands a clock draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandSet {
+    pub hour: bool,
+    pub minute: bool,
+    pub second: bool,
+}
+
+impl HandSet {
+    pub const ALL: HandSet = HandSet {
+        hour: true,
+        minute: true,
+        second: true,
+    };
+    pub const NO_SECONDS: HandSet = HandSet {
+        second: false,
+        ..HandSet::ALL
+    };
+    pub const NONE: HandSet = HandSet {
+        hour: false,
+        minute: false,
+        second: false,
+    };
+}
+
+impl Default for HandSet {
+    fn default() -> Self {
+        HandSet::ALL
+    }
+}
+
+/// Colours and plotting symbol of a clock face.
+#[derive(Debug, Clone, Copy)]
+pub struct DialStyle {
+    pub hour: Color,
+    pub minute: Color,
+    pub second: Color,
+    pub marks: Color,
+    /// When set, the marks shade from `marks` at 12 o'clock round to this.
+    pub marks_to: Option<Color>,
+    pub face: Face,
+    /// Symbol for the hands.
+    pub marker: Marker,
+    /// Symbol for dots and ticks, `None` hides them. Numerals always show.
+    pub mark_marker: Option<Marker>,
+    pub hour_length: f64,
+    pub minute_length: f64,
+    pub second_length: f64,
+    /// Hand widths for the filled shapes, as a fraction of the dial radius.
+    pub hour_width: f64,
+    pub minute_width: f64,
+    pub second_width: f64,
+    pub hour_shape: HandShape,
+    pub minute_shape: HandShape,
+    pub second_shape: HandShape,
+}
+
+impl Default for DialStyle {
+    fn default() -> Self {
+        DialStyle {
+            hour: Color::Red,
+            minute: Color::Green,
+            second: Color::Blue,
+            marks: Color::White,
+            marks_to: None,
+            face: Face::Dots,
+            marker: Marker::Braille,
+            mark_marker: Some(Marker::Dot),
+            hour_length: 0.5,
+            minute_length: 0.65,
+            second_length: 0.8,
+            hour_width: 0.12,
+            minute_width: 0.09,
+            second_width: 0.06,
+            hour_shape: HandShape::Line,
+            minute_shape: HandShape::Line,
+            second_shape: HandShape::Line,
+        }
+    }
+}
+
+/// Extra points or lines drawn between the marks and the hands, e.g. a
+/// sub-dial or a progress arc.
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub points: Vec<(f64, f64)>,
+    pub graph: GraphType,
+    pub marker: Marker,
+    pub color: Color,
+}
+
+/// The static parts of a clock face.
+#[derive(Debug, Clone)]
+pub struct Dial {
+    marks: Vec<(f64, f64)>,
+    ticks: Vec<[(f64, f64); 2]>,
+    numerals: Vec<((f64, f64), String)>,
+    pub style: DialStyle,
+}
+
+impl Dial {
+    pub fn new(style: DialStyle) -> Dial {
+        const ROMAN: [&str; 12] = [
+            "XII", "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI",
+        ];
+        let at = |turn: f64, radius: f64| -> (f64, f64) { (dial_rot(turn) * radius).into() };
+        let mut dial = Dial {
+            marks: Vec::new(),
+            ticks: Vec::new(),
+            numerals: Vec::new(),
+            style,
+        };
+        match style.face {
+            Face::Dots => dial.marks = (0..12).map(|i| at(i as f64 / 12.0, 0.9)).collect(),
+            Face::Arabic => {
+                dial.numerals = (0..12)
+                    .map(|i| {
+                        (
+                            at(i as f64 / 12.0, 0.85),
+                            (if i == 0 { 12 } else { i }).to_string(),
+                        )
+                    })
+                    .collect()
+            }
+            Face::Roman => {
+                dial.numerals = (0..12)
+                    .map(|i| (at(i as f64 / 12.0, 0.85), ROMAN[i].to_string()))
+                    .collect()
+            }
+            Face::Ticks => {
+                for i in 0..60 {
+                    let inner = if i % 5 == 0 { 0.78 } else { 0.9 };
+                    let turn = i as f64 / 60.0;
+                    dial.ticks.push([at(turn, inner), at(turn, 0.95)]);
+                }
+            }
+            Face::TwentyFour => {
+                for i in 0..24 {
+                    let turn = i as f64 / 24.0;
+                    if i % 2 == 0 {
+                        dial.numerals.push((at(turn, 0.85), i.to_string()));
+                    } else {
+                        dial.marks.push(at(turn, 0.9));
+                    }
+                }
+            }
+            Face::Plain => {}
+        }
+        dial
+    }
+
+    /// Hands for the local `time`, on a 12 or 24 hour dial as the face says.
+    pub fn hands(&self, time: OffsetDateTime, motion: Motion) -> Hands {
+        Hands::on_dial(seconds_of_day(time, motion), self.style.face.hours())
+    }
+
+    /// Draws the marks, then `layers`, then the hands in `set` on top. The
+    /// hands cross over numerals.
+    pub fn draw(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        hands: Option<&Hands>,
+        set: HandSet,
+        layers: &[Layer],
+    ) {
+        let mut hand_shapes = Vec::new();
+        if let Some(hands) = hands {
+            let style = &self.style;
+            for (shown, dir, length, width, shape, color) in [
+                (
+                    set.hour,
+                    hands.hour,
+                    style.hour_length,
+                    style.hour_width,
+                    style.hour_shape,
+                    style.hour,
+                ),
+                (
+                    set.minute,
+                    hands.minute,
+                    style.minute_length,
+                    style.minute_width,
+                    style.minute_shape,
+                    style.minute,
+                ),
+                (
+                    set.second,
+                    hands.second,
+                    style.second_length,
+                    style.second_width,
+                    style.second_shape,
+                    style.second,
+                ),
+            ] {
+                if !shown {
+                    continue;
+                }
+                match shape.polygon(dir, length, width) {
+                    Some(polygon) => {
+                        let points = fill(&polygon, area, style.marker);
+                        hand_shapes.push((points, GraphType::Scatter, color));
+                    }
+                    None => {
+                        let line: Vec<(f64, f64)> = vec![(dir * length).into(), Vtx2::new().into()];
+                        hand_shapes.push((line, GraphType::Line, color));
+                    }
+                }
+            }
+        }
+
+        let mark_style = |point: (f64, f64)| {
+            let color = match self.style.marks_to {
+                Some(to) => blend(self.style.marks, to, turn_of(point)),
+                None => self.style.marks,
+            };
+            Style::default().fg(color)
+        };
+        for (point, text) in &self.numerals {
+            label(area, buf, *point, text, mark_style(*point));
+        }
+
+        let mut chartdata = Vec::new();
+        // ticks are thin lines, so they use the finer symbol of the hands
+        if let Some(marker) = self.style.mark_marker {
+            if self.style.marks_to.is_none() {
+                let marks_set = Dataset::default()
+                    .graph_type(GraphType::Scatter)
+                    .marker(marker)
+                    .style(mark_style((0.0, 1.0)))
+                    .data(self.marks.as_ref());
+                chartdata.push(marks_set);
+            } else {
+                for point in &self.marks {
+                    let mark_set = Dataset::default()
+                        .graph_type(GraphType::Scatter)
+                        .marker(marker)
+                        .style(mark_style(*point))
+                        .data(std::slice::from_ref(point));
+                    chartdata.push(mark_set);
+                }
+            }
+            for tick in &self.ticks {
+                let tick_set = Dataset::default()
+                    .graph_type(GraphType::Line)
+                    .marker(self.style.marker)
+                    .style(mark_style(tick[1]))
+                    .data(tick);
+                chartdata.push(tick_set);
+            }
+        }
+        for layer in layers {
+            let layer_set = Dataset::default()
+                .graph_type(layer.graph)
+                .marker(layer.marker)
+                .style(Style::default().fg(layer.color))
+                .data(layer.points.as_ref());
+            chartdata.push(layer_set);
+        }
+        for (points, graph, color) in &hand_shapes {
+            let hand_set = Dataset::default()
+                .graph_type(*graph)
+                .marker(self.style.marker)
+                .style(Style::default().fg(*color))
+                .data(points);
+            chartdata.push(hand_set);
+        }
+
+        let (x_bounds, y_bounds) = bounds(area);
+        let chart = Chart::new(chartdata)
+            .block(Block::default())
+            .x_axis(Axis::default().bounds(x_bounds))
+            .y_axis(Axis::default().bounds(y_bounds));
+
+        chart.render(area, buf);
+    }
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::new(DialStyle::default())
+    }
+}
@@ -0,0 +1,145 @@
+// ====================================
+// ===== Hand shapes
+// ====================================
+
+use tui::{layout::Rect, symbols::Marker};
+
+use vtx::Vtx2;
+
+use crate::dial::bounds;
+
+/// How a hand is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HandShape {
+    /// A single line from the centre.
+    #[default]
+    Line,
+    /// Wide at the centre, narrowing to a point.
+    Tapered,
+    /// A narrow shaft with an arrow head.
+    Arrow,
+    /// A needle with a weight on a short tail behind the centre.
+    Counterweight,
+}
+
+impl HandShape {
+    /// Corners of the hand pointing along `dir`, in dial coordinates, or
+    /// `None` for `Line`. `width` is the widest part across the hand.
+    ///
+    /// The shape is laid out along +x, then turned so +x points along `dir`.
+    pub fn polygon(self, dir: Vtx2, length: f64, width: f64) -> Option<Vec<Vtx2>> {
+        let (l, w) = (length, width / 2.0);
+        let corners: &[(f64, f64)] = match self {
+            HandShape::Line => return None,
+            HandShape::Tapered => &[(-w, 0.0), (0.0, -w), (l, 0.0), (0.0, w)],
+            HandShape::Arrow => {
+                let (shaft, head) = (w / 3.0, l * 0.75);
+                &[
+                    (0.0, -shaft),
+                    (head, -shaft),
+                    (head, -w),
+                    (l, 0.0),
+                    (head, w),
+                    (head, shaft),
+                    (0.0, shaft),
+                ]
+            }
+            HandShape::Counterweight => {
+                let needle = w / 4.0;
+                &[
+                    (l, 0.0),
+                    (-l * 0.1, needle),
+                    (-l * 0.18, w),
+                    (-l * 0.3, 0.0),
+                    (-l * 0.18, -w),
+                    (-l * 0.1, -needle),
+                ]
+            }
+        };
+        let along = Vtx2::from_rot(dir.as_rot());
+        let across = Vtx2::build(-along.y, along.x);
+        Some(
+            corners
+                .iter()
+                .map(|&(x, y)| (along * x) + (across * y))
+                .collect(),
+        )
+    }
+}
+
+/// Points covering `polygon` and its edges when drawn into `area` with
+/// `marker`: one per braille dot inside, or per cell for the others.
+///
+/// They all go into one dataset, as the chart draws each dataset over the
+/// cells of the ones before it.
+pub fn fill(polygon: &[Vtx2], area: Rect, marker: Marker) -> Vec<(f64, f64)> {
+    let (dots_x, dots_y) = match marker {
+        Marker::Braille => (2, 4),
+        Marker::Dot | Marker::Block => (1, 1),
+    };
+    let cols = area.width as usize * dots_x;
+    let rows = area.height as usize * dots_y;
+    if cols < 2 || rows < 2 || polygon.len() < 3 {
+        return Vec::new();
+    }
+    let (x_bounds, y_bounds) = bounds(area);
+    // sample each grid point half a step in, so rounding never moves it
+    // into its neighbour
+    let grid = |i: usize, n: usize| ((i as f64 + 0.5) / (n - 1) as f64).min(1.0);
+    let mut points = Vec::new();
+    let mut crossings = Vec::new();
+    for row in 0..rows {
+        let y = y_bounds[1] - grid(row, rows) * (y_bounds[1] - y_bounds[0]);
+        crossings.clear();
+        for (a, b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+            if (a.y <= y) != (b.y <= y) {
+                crossings.push(a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x));
+            }
+        }
+        crossings.sort_by(f64::total_cmp);
+        for span in crossings.chunks_exact(2) {
+            for col in 0..cols {
+                let x = x_bounds[0] + grid(col, cols) * (x_bounds[1] - x_bounds[0]);
+                if (span[0]..=span[1]).contains(&x) {
+                    points.push((x, y));
+                }
+            }
+        }
+    }
+    // edges, so parts thinner than a dot still show
+    let step = ((x_bounds[1] - x_bounds[0]) / cols as f64)
+        .min((y_bounds[1] - y_bounds[0]) / rows as f64)
+        / 2.0;
+    for (&a, &b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        let n = (a.dist(b) / step).ceil().max(1.0) as usize;
+        for i in 0..=n {
+            points.push((a + (b - a) * (i as f64 / n as f64)).into());
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_point_along_the_hand() {
+        let up = Vtx2::build(0.0, 1.0);
+        assert_eq!(HandShape::Line.polygon(up, 0.5, 0.1), None);
+        for shape in [
+            HandShape::Tapered,
+            HandShape::Arrow,
+            HandShape::Counterweight,
+        ] {
+            let polygon = shape.polygon(up, 0.5, 0.1).unwrap();
+            let tip = polygon
+                .iter()
+                .copied()
+                .max_by(|a, b| a.y.total_cmp(&b.y))
+                .unwrap();
+            assert!(tip.dist(Vtx2::build(0.0, 0.5)) < 1e-9, "{:?}", shape);
+            assert!(polygon.iter().all(|v| v.x.abs() <= 0.05 + 1e-9));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// ====================================
+// ===== Completed cycle history
+// ====================================
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use time::{Date, OffsetDateTime};
+
+use crate::config::data_dir;
+use crate::cycle::PhaseKind;
+use crate::stopwatch::format_duration;
+use tock::zone::Zone;
+
+/// One completed phase, stored as a CSV line:
+/// `unix_seconds,program,phase,duration_seconds`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub finished: OffsetDateTime,
+    pub program: String,
+    pub kind: PhaseKind,
+    pub duration: Duration,
+}
+
+/// Totals for one calendar day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Day {
+    pub active: Duration,
+    pub phases: usize,
+}
+
+/// `$XDG_DATA_HOME/tock/history.csv`, else `~/.local/share/tock/history.csv`.
+pub fn default_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("history.csv"))
+}
+
+pub fn append(path: &Path, entry: &Entry) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{},{},{},{}",
+        entry.finished.unix_timestamp(),
+        entry.program,
+        entry.kind.name(),
+        entry.duration.as_secs()
+    )
+}
+
+/// Reads every entry; lines that do not parse are skipped.
+pub fn read(path: &Path) -> io::Result<Vec<Entry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(text.lines().filter_map(parse_line).collect())
+}
+
+fn parse_line(line: &str) -> Option<Entry> {
+    let mut fields = line.split(',');
+    let finished = OffsetDateTime::from_unix_timestamp(fields.next()?.parse().ok()?).ok()?;
+    let program = fields.next()?.to_string();
+    let kind = PhaseKind::parse(fields.next()?)?;
+    let duration = Duration::from_secs(fields.next()?.parse().ok()?);
+    Some(Entry {
+        finished,
+        program,
+        kind,
+        duration,
+    })
+}
+
+/// Sums entries per calendar day in `zone`.
+pub fn daily_totals(entries: &[Entry], zone: &Zone) -> BTreeMap<Date, Day> {
+    let mut days: BTreeMap<Date, Day> = BTreeMap::new();
+    for entry in entries {
+        let day = days.entry(zone.at(entry.finished).date()).or_default();
+        day.phases += 1;
+        if entry.kind.is_active() {
+            day.active += entry.duration;
+        }
+    }
+    days
+}
+
+/// `tock history`: one line per day.
+pub fn print_totals(path: &Path, zone: &Zone) -> io::Result<()> {
+    let entries = read(path)?;
+    if entries.is_empty() {
+        println!("no completed cycles in {}", path.display());
+        return Ok(());
+    }
+    println!("{:<12}{:>12}{:>8}", "date", "focus", "phases");
+    for (date, day) in daily_totals(&entries, zone) {
+        println!(
+            "{:<12}{:>12}{:>8}",
+            date.to_string(),
+            format_duration(day.active),
+            day.phases
+        );
+    }
+    Ok(())
+}
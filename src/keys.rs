@@ -0,0 +1,230 @@
+// ====================================
+// ===== Key bindings
+// ====================================
+
+use std::fmt;
+
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Deserializer};
+
+use tock::complication::Complication;
+
+/// A single key as written in the config file: `q`, `up`, `esc`, `f1`, ...
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key(pub KeyCode);
+
+impl Key {
+    pub fn parse(s: &str) -> Result<Key, String> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key(KeyCode::Char(c)));
+        }
+        let code = match s.to_ascii_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                _ => return Err(format!("unknown key '{}'", s)),
+            },
+        };
+        Ok(Key(code))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Key::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Where an action does something.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Everywhere,
+    Stopwatch,
+    Timer,
+    Cycle,
+    /// While an alarm rings.
+    Alarm,
+}
+
+/// Which key triggers which action.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keys {
+    pub quit: Key,
+    pub help: Key,
+    pub mode: Key,
+    pub theme: Key,
+    pub sweep: Key,
+    pub face: Key,
+    pub lock_aspect: Key,
+    pub taller: Key,
+    pub shorter: Key,
+    pub wider: Key,
+    pub narrower: Key,
+    pub start_stop: Key,
+    pub lap: Key,
+    pub reset: Key,
+    pub add_minute: Key,
+    pub skip: Key,
+    pub add_alarm: Key,
+    pub snooze: Key,
+    pub dismiss: Key,
+    pub digital: Key,
+    pub date: Key,
+    pub weekday: Key,
+    pub week: Key,
+    pub am_pm: Key,
+}
+
+impl Keys {
+    /// Every action as `(name in the config, scope, description, key)`, in
+    /// the order the help lists them.
+    pub fn actions(&self) -> Vec<(&'static str, Scope, &'static str, Key)> {
+        use Scope::*;
+        vec![
+            ("quit", Everywhere, "quit", self.quit),
+            ("help", Everywhere, "show or hide this help", self.help),
+            (
+                "mode",
+                Everywhere,
+                "next mode: clock, stopwatch, ...",
+                self.mode,
+            ),
+            ("theme", Everywhere, "next colour theme", self.theme),
+            ("sweep", Everywhere, "sweeping or ticking hands", self.sweep),
+            ("face", Everywhere, "next dial face", self.face),
+            (
+                "lock_aspect",
+                Everywhere,
+                "keep the dial round",
+                self.lock_aspect,
+            ),
+            ("taller", Everywhere, "make the dial taller", self.taller),
+            ("shorter", Everywhere, "make the dial shorter", self.shorter),
+            ("wider", Everywhere, "make the dial wider", self.wider),
+            (
+                "narrower",
+                Everywhere,
+                "make the dial narrower",
+                self.narrower,
+            ),
+            ("add_alarm", Everywhere, "add an alarm", self.add_alarm),
+            ("digital", Everywhere, "digital readout", self.digital),
+            ("date", Everywhere, "date", self.date),
+            ("weekday", Everywhere, "day of the week", self.weekday),
+            ("week", Everywhere, "week number", self.week),
+            ("am_pm", Everywhere, "AM / PM", self.am_pm),
+            ("start_stop", Stopwatch, "start / stop", self.start_stop),
+            ("lap", Stopwatch, "lap", self.lap),
+            ("reset", Stopwatch, "reset", self.reset),
+            ("start_stop", Timer, "pause / resume", self.start_stop),
+            ("add_minute", Timer, "add a minute", self.add_minute),
+            ("start_stop", Cycle, "pause / resume", self.start_stop),
+            ("skip", Cycle, "skip to the next phase", self.skip),
+            ("snooze", Alarm, "snooze", self.snooze),
+            ("dismiss", Alarm, "dismiss", self.dismiss),
+        ]
+    }
+
+    /// Fails when two actions share a key. Even actions for different modes
+    /// count, as a key press reaches the mode before the general actions.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen: Vec<(&str, Key)> = Vec::new();
+        for (name, _, _, key) in self.actions() {
+            match seen.iter().find(|&&(other, k)| k == key && other != name) {
+                Some((other, _)) => {
+                    return Err(format!(
+                        "keys.{} and keys.{} are both '{}'",
+                        other, name, key
+                    ))
+                }
+                None => seen.push((name, key)),
+            }
+        }
+        Ok(())
+    }
+
+    /// The complication `key` shows or hides, if any.
+    pub fn complication(&self, key: Key) -> Option<Complication> {
+        [
+            (self.digital, Complication::Digital),
+            (self.date, Complication::Date),
+            (self.weekday, Complication::Weekday),
+            (self.week, Complication::Week),
+            (self.am_pm, Complication::AmPm),
+        ]
+        .into_iter()
+        .find(|&(k, _)| k == key)
+        .map(|(_, complication)| complication)
+    }
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Keys {
+            quit: Key(KeyCode::Char('q')),
+            help: Key(KeyCode::Char('?')),
+            mode: Key(KeyCode::Char('m')),
+            theme: Key(KeyCode::Char('t')),
+            sweep: Key(KeyCode::Char('s')),
+            face: Key(KeyCode::Char('f')),
+            lock_aspect: Key(KeyCode::Char('a')),
+            taller: Key(KeyCode::Up),
+            shorter: Key(KeyCode::Down),
+            wider: Key(KeyCode::Right),
+            narrower: Key(KeyCode::Left),
+            start_stop: Key(KeyCode::Char(' ')),
+            lap: Key(KeyCode::Char('l')),
+            reset: Key(KeyCode::Char('r')),
+            add_minute: Key(KeyCode::Char('+')),
+            skip: Key(KeyCode::Char('n')),
+            add_alarm: Key(KeyCode::Char('@')),
+            snooze: Key(KeyCode::Char('z')),
+            dismiss: Key(KeyCode::Char('x')),
+            digital: Key(KeyCode::Char('1')),
+            date: Key(KeyCode::Char('2')),
+            weekday: Key(KeyCode::Char('3')),
+            week: Key(KeyCode::Char('4')),
+            am_pm: Key(KeyCode::Char('5')),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Analog clock faces for [tui](https://docs.rs/tui) applications.
+//!
+//! [`ClockWidget`] draws a clock into any `Rect`; the `tock` binary is built
+//! on top of it.
+
+pub mod complication;
+pub mod dial;
+pub mod hand;
+pub mod source;
+pub mod theme;
+pub mod widget;
+pub mod zone;
+
+pub use complication::{Complication, Complications, Slot};
+pub use dial::{Dial, DialStyle, Face, HandSet, Hands, Layer, Motion};
+pub use hand::HandShape;
+pub use source::{FixedClock, ScaledClock, SystemClock, TimeSource};
+pub use theme::{ColorDepth, Theme};
+pub use widget::ClockWidget;
+pub use zone::Zone;
@@ -1,257 +1,233 @@
 // #![allow(unused)]
-use time::macros::offset;
-use time::OffsetDateTime;
-
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+mod alarm;
+mod app;
+mod cli;
+mod config;
+mod countdown;
+mod cycle;
+mod history;
+mod keys;
+mod overlay;
+#[cfg(test)]
+mod snapshots;
+mod stopwatch;
+mod terminal;
+mod world;
+
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use std::{
-    io, thread,
+    io::{self, Write},
     time::{Duration, Instant},
 };
-use tui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Style},
-    symbols::Marker,
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
-    Terminal,
-};
-
-// println!("start");
-// let raw_time = OffsetDateTime::now_utc();
-// let offset_time = raw_time.to_offset(offset!(+1));
-// let (h, m, s) = offset_time.to_hms();
-// println!("now is {}, {}, {}", h, m, s);
-//
 
-use vtx::Vtx2;
+use clap::Parser;
+
+use alarm::Alarms;
+use app::{App, Mode};
+use cli::{Cli, Command};
+use config::{Config, Watcher};
+use countdown::Countdown;
+use cycle::{Cycle, Program};
+use overlay::FrameCounter;
+use stopwatch::{ExportFormat, Stopwatch};
+use terminal::{Signals, TerminalGuard};
+use tock::zone::Zone;
+use world::WorldClock;
+
+/// Longest the main loop sleeps, so alarms and changes to the config and
+/// alarm files are noticed even when nothing is drawn.
+const IDLE_WAKEUP: Duration = Duration::from_millis(500);
+
+/// Rings the terminal bell.
+fn bell<W: Write>(out: &mut W) -> io::Result<()> {
+    out.write_all(b"\x07")?;
+    out.flush()
+}
 
 fn main() -> Result<(), io::Error> {
-    // setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-    let mut center_height = 40;
-    let mut center_width = 80;
+    // parse arguments before touching the terminal so errors stay readable
+    let cli = Cli::parse();
+    let config_path = cli.config.clone().or_else(config::default_path);
+    let (config, config_error) = match config_path.as_deref().map(Config::load) {
+        Some(Ok(config)) => (config, None),
+        Some(Err(e)) => (Config::default(), Some(e)),
+        None => (Config::default(), None),
+    };
+    let mut watcher = config_path.map(Watcher::new);
+    let zone = cli.zone.or(config.zone).unwrap_or_else(Zone::local);
+    let source = cli.time_source();
+    let mut export = None;
+    let mode = match cli.command.clone() {
+        None | Some(Command::Clock) => Mode::Clock,
+        Some(Command::World { mut zones }) => {
+            if zones.is_empty() {
+                zones = config.world.clone();
+            }
+            if zones.is_empty() {
+                zones.push(zone);
+            }
+            Mode::World(WorldClock::new(zones, zone))
+        }
+        Some(Command::Stopwatch {
+            export: path,
+            format,
+        }) => {
+            export = path.map(|path| {
+                let format = format.unwrap_or_else(|| ExportFormat::from_path(&path));
+                (path, format)
+            });
+            Mode::Stopwatch(Stopwatch::new())
+        }
+        Some(Command::Timer { duration }) => {
+            Mode::Timer(Countdown::new(duration, source.instant()))
+        }
+        Some(Command::Pomodoro {
+            work,
+            short_break,
+            long_break,
+            rounds,
+        }) => {
+            let program = Program::pomodoro(work, short_break, long_break, rounds as usize);
+            Mode::Cycle(Cycle::new(program, source.instant()))
+        }
+        Some(Command::Interval { program }) => Mode::Cycle(Cycle::new(program, source.instant())),
+        Some(Command::History) => {
+            return match history::default_path() {
+                Some(path) => history::print_totals(&path, &zone),
+                None => Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "no home directory for the history file",
+                )),
+            };
+        }
+        Some(Command::Alarm { action }) => {
+            let Some(path) = alarm::default_path() else {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "no home directory for the alarm file",
+                ));
+            };
+            if let Err(e) = alarm::run(action, &path) {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+    };
+    // a theme from --theme may be missing from the config
+    if let Err(e) = config.theme(cli.theme.as_deref()) {
+        eprintln!("error: {}", e);
+        std::process::exit(2);
+    }
+    let mut app = App::new(&cli, config, mode);
+    app.config_error = config_error;
+    app.history_path = history::default_path();
+    app.alarms = Alarms::new(alarm::default_path());
+    if cli.frames {
+        app.frame_counter = Some(FrameCounter::new(Instant::now()));
+    }
 
-    //time stuff
-    let mut raw_time; //= OffsetDateTime::now_utc();
-    let mut offset_time; //= raw_time.to_offset(offset!(+1));
-    let (mut h, mut m, mut s); // = offset_time.to_hms();
+    // setup terminal, restored when `terminal` is dropped or on a panic
+    let signals = Signals::new()?;
+    let mut terminal = TerminalGuard::new()?;
 
-    // vtx stuff
-    let c_hand = Vtx2::new();
-    let mut h_hand; // = Vtx2::new();
-    let mut m_hand; // = Vtx2::new();
-    let mut s_hand; // = Vtx2::new();
-    let mut marks: Vec<(f64, f64)> = Vec::new();
+    // ========== start main loop ==========
+    // Sleeps until input arrives or the next frame would look different: an
+    // idle ticking clock draws once a second and wakes at most twice, which
+    // keeps it well under 1% of a CPU. `--frames` shows the counts.
+    let min_frame = Duration::from_millis(cli.refresh);
+    let mut redraw = true;
+    let mut next_frame = Instant::now();
+    loop {
+        let wake = Instant::now();
+        if let Some(counter) = &mut app.frame_counter {
+            counter.wakeup(wake);
+        }
 
-    for i in 0..12 {
-        let nm = Vtx2::from_rot(
-            (i as f64) * -1.0 * (2.0 * std::f64::consts::PI / 12.0) + (std::f64::consts::PI / 2.0),
-        );
+        // ========== signals ==========
+        if let Some(signal) = signals.terminated() {
+            app.exit_code = 128 + signal;
+            break;
+        }
+        if signals.take_suspend() {
+            terminal.suspend()?;
+            redraw = true;
+        }
 
-        marks.push((nm * 0.9).into());
-    }
+        // ========== config reload ==========
+        if let Some(reloaded) = watcher.as_mut().and_then(Watcher::poll) {
+            app.reload(reloaded);
+            redraw = true;
+        }
+        if let Some(reloaded) = app.alarms.poll() {
+            app.alarm_error = reloaded.err();
+            redraw = true;
+        }
 
-    let mut timer = Instant::now();
+        // ========== calculate stuff ===========
+        if app.tick(source.as_ref()) {
+            bell(terminal.backend_mut())?;
+            redraw = true;
+        }
+        if app.quit {
+            break;
+        }
 
-    // ========== start main loop ==========
-    loop {
-        let size = terminal.get_frame().size();
-        let frame = size;
-        let term_height = frame.height;
-        let term_width = frame.width;
+        // ========== Drawing ==========
+        if redraw || wake >= next_frame {
+            let (now, instant) = (source.now(), source.instant());
+            if let Some(counter) = &mut app.frame_counter {
+                counter.frame();
+            }
+            terminal.draw(|f| app.draw(f, now, instant))?;
+            redraw = false;
+            let wait = match (app.next_change(now, instant), source.speed()) {
+                (Some(wait), speed) if speed > 0.0 => wait.div_f64(speed),
+                _ => IDLE_WAKEUP,
+            };
+            next_frame = Instant::now() + wait.max(min_frame);
+        }
 
-        if event::poll(Duration::from_millis(0)).unwrap_or(false) {
-            match event::read().unwrap() {
-                event::Event::Key(ev) => match ev.code {
-                    KeyCode::Char('q') => break,
-                    KeyCode::Up => {
-                        if center_height < term_height {
-                            center_height += 1;
-                        }
+        // ========== input ==========
+        let timeout = next_frame
+            .saturating_duration_since(Instant::now())
+            .min(IDLE_WAKEUP);
+        if event::poll(timeout)? {
+            match event::read()? {
+                // raw mode turns Ctrl-C and Ctrl-Z into plain key presses
+                Event::Key(ev) if ev.modifiers.contains(KeyModifiers::CONTROL) => match ev.code {
+                    KeyCode::Char('c') => {
+                        app.quit = true;
+                        app.exit_code = 130;
                     }
-                    KeyCode::Down => {
-                        if center_height > 0 {
-                            center_height -= 1;
-                        }
-                    }
-                    KeyCode::Right => {
-                        if center_width < term_width {
-                            center_width += 1;
-                        }
-                    }
-                    KeyCode::Left => {
-                        if center_width > 0 {
-                            center_width -= 1;
-                        }
+                    KeyCode::Char('z') => {
+                        terminal.suspend()?;
+                        redraw = true;
                     }
                     _ => {}
                 },
+                Event::Key(ev) => {
+                    app.key(ev.code, terminal.size()?, source.as_ref());
+                    redraw = true;
+                }
+                Event::Resize(..) => redraw = true,
                 _ => {}
             }
-        } else {
-            // ========== calculate stuff ===========
-            raw_time = OffsetDateTime::now_utc();
-            offset_time = raw_time.to_offset(offset!(+1));
-            (h, m, s) = offset_time.to_hms();
-
-            h_hand = Vtx2::from_rot(
-                (h as f64) * -1.0 * (2.0 * std::f64::consts::PI / 12.0)
-                    + (std::f64::consts::PI / 2.0),
-            );
-            h_hand = h_hand * 0.5;
-
-            m_hand = Vtx2::from_rot(
-                (m as f64) * -1.0 * (2.0 * std::f64::consts::PI / 60.0)
-                    + (std::f64::consts::PI / 2.0),
-            );
-            m_hand = m_hand * 0.65;
-
-            s_hand = Vtx2::from_rot(
-                (s as f64) * -1.0 * (2.0 * std::f64::consts::PI / 60.0)
-                    + (std::f64::consts::PI / 2.0),
-            );
-            s_hand = s_hand * 0.8;
-
-            // let data = vec![(1.0, 1.0), (3.0, 5.0), (10.0, 10.0)];
-
-            let c_data: (f64, f64) = c_hand.into();
-            let h_data: (f64, f64) = h_hand.into();
-            let m_data: (f64, f64) = m_hand.into();
-            let s_data: (f64, f64) = s_hand.into();
-            let h: &[(f64, f64)] = &[h_data, c_data];
-            let m: &[(f64, f64)] = &[m_data, c_data];
-            let s: &[(f64, f64)] = &[s_data, c_data];
-
-            let marks_set = Dataset::default()
-                .graph_type(GraphType::Scatter)
-                .marker(Marker::Dot)
-                .style(Style::default().fg(Color::White))
-                // .name("testdata")
-                .data(marks.as_ref());
-            let hour_set = Dataset::default()
-                .graph_type(GraphType::Line)
-                .marker(Marker::Braille)
-                .style(Style::default().fg(Color::Red))
-                // .name("testdata")
-                .data(h);
-            let minute_set = Dataset::default()
-                .graph_type(GraphType::Line)
-                .marker(Marker::Braille)
-                .style(Style::default().fg(Color::Green))
-                // .name("testdata")
-                .data(m);
-            let second_set = Dataset::default()
-                .graph_type(GraphType::Line)
-                .marker(Marker::Braille)
-                .style(Style::default().fg(Color::Blue))
-                // .name("testdata")
-                .data(s);
-
-            // ========== Drawing ==========
-
-            let width = if term_width < center_width {
-                term_width
-            } else {
-                center_width
-            };
-
-            let height = if term_height < center_height {
-                term_height
-            } else {
-                center_height
-            };
-
-            let top_btm = (term_height - height) / 2;
-            let left_right = (term_width - width) / 2;
-            let lay_vert_constr = vec![
-                Constraint::Length(top_btm),
-                Constraint::Length(height),
-                Constraint::Length(top_btm),
-            ];
-
-            let lay_hori_constr = vec![
-                Constraint::Length(left_right),
-                Constraint::Length(width),
-                Constraint::Length(left_right),
-            ];
-
-            terminal.draw(|f| {
-                let size = f.size();
-                let lay_vert = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(1)
-                    .constraints(lay_vert_constr.clone())
-                    .split(size);
-                let lay_hori = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .margin(1)
-                    .constraints(lay_hori_constr.clone())
-                    .split(lay_vert[1]);
-
-                // let timeval = format!(
-                //     "time is {} => fps is {}",
-                //     timer.elapsed().as_micros(),
-                //     1.0 / (timer.elapsed().as_micros() as f64 ) * 1000000.0
-                // );
-                timer = Instant::now();
-                let block = Block::default().title("Tock").borders(Borders::ALL);
-
-                let chartdata = vec![marks_set, hour_set, minute_set, second_set];
-
-                let chart = Chart::new(chartdata)
-                    .block(Block::default() /* .title("chart") */)
-                    .x_axis(
-                        Axis::default()
-                            // .title(Span::styled("X Axis", Style::default().fg(Color::Red)))
-                            // .style(Style::default().fg(Color::White))
-                            .bounds([-1.0, 1.0]),
-                        // .labels(
-                        //     ["0.0", "5.0", "10.0"]
-                        //         .iter()
-                        //         .cloned()
-                        //         .map(Span::from)
-                        //         .collect(),
-                        // ),
-                    )
-                    .y_axis(
-                        Axis::default()
-                            // .title(Span::styled("Y Axis", Style::default().fg(Color::Red)))
-                            // .style(Style::default().fg(Color::White))
-                            .bounds([-1.0, 1.0]),
-                        // .labels(
-                        //     ["0.0", "5.0", "10.0"]
-                        //         .iter()
-                        //         .cloned()
-                        //         .map(Span::from)
-                        //         .collect(),
-                        // ),
-                    );
-
-                // f.render_widget(block, lay_hori[1]);
-                f.render_widget(block, lay_hori[1]);
-                f.render_widget(chart, lay_hori[1]);
-            })?;
+            if app.quit {
+                break;
+            }
         }
-        thread::sleep(Duration::from_millis(20));
     }
 
     // ========== End of Main loop ==========
-    // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    drop(terminal);
+
+    if let (Some(stopwatch), Some((path, format))) = (app.stopwatch(), &export) {
+        stopwatch.export(path, *format)?;
+    }
+    if app.exit_code != 0 {
+        std::process::exit(app.exit_code);
+    }
 
     Ok(())
 }
@@ -0,0 +1,219 @@
+// ====================================
+// ===== Overlays drawn above the dial
+// ====================================
+
+use std::time::Instant;
+
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use tock::theme::Theme;
+
+/// A box of `width` x `height` cells centred in `area`, clipped to it.
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Box in the error colour with `message`, wrapped, in the lower part of
+/// `area`.
+pub fn error<B: Backend>(f: &mut Frame<B>, area: Rect, theme: &Theme, title: &str, message: &str) {
+    message_box(f, area, title, message, theme.error, false);
+}
+
+/// Box in the notice colour with `message` in the upper part of `area`.
+pub fn notice<B: Backend>(f: &mut Frame<B>, area: Rect, theme: &Theme, title: &str, message: &str) {
+    message_box(f, area, title, message, theme.notice, true);
+}
+
+fn message_box<B: Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    title: &str,
+    message: &str,
+    color: Color,
+    top: bool,
+) {
+    let width = area.width.saturating_sub(4).min(72);
+    let text_width = width.saturating_sub(2).max(1) as usize;
+    let lines: u16 = message
+        .lines()
+        .map(|line| (line.chars().count() / text_width + 1) as u16)
+        .sum();
+    let height = (lines + 2).min(area.height);
+    let y = if top {
+        area.y
+    } else {
+        area.y + area.height - height
+    };
+    let rect = Rect {
+        y,
+        ..centered(area, width, height)
+    };
+
+    let style = Style::default().fg(color);
+    let paragraph = Paragraph::new(message)
+        .style(style)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(style),
+        );
+    f.render_widget(Clear, rect);
+    f.render_widget(paragraph, rect);
+}
+
+/// Box listing `bindings` as key and description, centred in `area`.
+pub fn help<B: Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    bindings: &[(String, &str)],
+) {
+    let key_width = bindings.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    let text: Vec<Spans> = bindings
+        .iter()
+        .map(|(key, description)| {
+            Spans::from(vec![
+                Span::styled(
+                    format!(" {:>width$}  ", key, width = key_width),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::raw(*description),
+            ])
+        })
+        .collect();
+    let width = (key_width + 3 + bindings.iter().map(|(_, d)| d.len()).max().unwrap_or(0))
+        .max(title.len()) as u16
+        + 4;
+    let rect = centered(area, width, bindings.len() as u16 + 2);
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.dim)),
+    );
+    f.render_widget(Clear, rect);
+    f.render_widget(paragraph, rect);
+}
+
+/// A one line text field with an optional error below it.
+#[derive(Debug, Clone, Default)]
+pub struct Prompt {
+    pub text: String,
+    pub error: Option<String>,
+}
+
+impl Prompt {
+    /// Applies an editing key; Enter and Esc are left to the caller.
+    pub fn edit(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.text.push(c),
+            KeyCode::Backspace => {
+                self.text.pop();
+            }
+            _ => return,
+        }
+        self.error = None;
+    }
+
+    pub fn render<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        theme: &Theme,
+        title: &str,
+        hint: &str,
+    ) {
+        let width = area.width.saturating_sub(4).min(72);
+        let rect = centered(area, width, 5);
+        let cursor = Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED));
+        let last = match &self.error {
+            Some(e) => Spans::from(Span::styled(e.as_str(), Style::default().fg(theme.error))),
+            None => Spans::from(Span::styled(hint, Style::default().fg(theme.dim))),
+        };
+        let text = vec![
+            Spans::from(vec![Span::raw(self.text.as_str()), cursor]),
+            Spans::default(),
+            last,
+        ];
+        let paragraph =
+            Paragraph::new(text).block(Block::default().title(title).borders(Borders::ALL));
+        f.render_widget(Clear, rect);
+        f.render_widget(paragraph, rect);
+    }
+}
+
+/// Redraw and wake-up counts for the `--frames` overlay, with rates over
+/// the last full second.
+#[derive(Debug, Clone)]
+pub struct FrameCounter {
+    frames: u64,
+    wakeups: u64,
+    window: (Instant, u64, u64),
+    rates: (f64, f64),
+}
+
+impl FrameCounter {
+    pub fn new(now: Instant) -> FrameCounter {
+        FrameCounter {
+            frames: 0,
+            wakeups: 0,
+            window: (now, 0, 0),
+            rates: (0.0, 0.0),
+        }
+    }
+
+    pub fn wakeup(&mut self, now: Instant) {
+        self.wakeups += 1;
+        let (start, frames, wakeups) = self.window;
+        let seconds = now.saturating_duration_since(start).as_secs_f64();
+        if seconds >= 1.0 {
+            self.rates = (
+                (self.frames - frames) as f64 / seconds,
+                (self.wakeups - wakeups) as f64 / seconds,
+            );
+            self.window = (now, self.frames, self.wakeups);
+        }
+    }
+
+    pub fn frame(&mut self) {
+        self.frames += 1;
+    }
+
+    /// Small box in the top right corner of `area`.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let text = format!(
+            "frames  {:>7} {:>5.1}/s\nwakeups {:>7} {:>5.1}/s",
+            self.frames, self.rates.0, self.wakeups, self.rates.1
+        );
+        let width = 26.min(area.width);
+        let height = 4.min(area.height);
+        let rect = Rect {
+            x: area.x + area.width - width,
+            y: area.y,
+            width,
+            height,
+        };
+        let paragraph =
+            Paragraph::new(text).block(Block::default().title("Frames").borders(Borders::ALL));
+        f.render_widget(Clear, rect);
+        f.render_widget(paragraph, rect);
+    }
+}
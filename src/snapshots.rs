@@ -0,0 +1,307 @@
+// ====================================
+// ===== Snapshot tests
+// ====================================
+
+//! Whole frames drawn into a `TestBackend` and compared with the text in
+//! `tests/snapshots/`. Run `TOCK_BLESS=1 cargo test` to write new or
+//! changed snapshots, then review them with `git diff`.
+
+use std::{env, fs, path::PathBuf, time::Duration};
+
+use clap::Parser;
+use crossterm::event::KeyCode;
+use time::macros::datetime;
+use tui::{backend::TestBackend, buffer::Buffer, layout::Rect, Terminal};
+
+use crate::alarm::Alarm;
+use crate::app::{App, Mode};
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::countdown::Countdown;
+use crate::cycle::{parse_interval, Cycle, Program};
+use crate::stopwatch::Stopwatch;
+use crate::world::WorldClock;
+use tock::complication::Complication;
+use tock::dial::Face;
+use tock::source::{FixedClock, TimeSource};
+use tock::zone::Zone;
+
+/// 10:08:37 UTC on a Sunday, the hands well apart.
+fn clock() -> FixedClock {
+    FixedClock::new(datetime!(2024-03-10 10:08:37 UTC))
+}
+
+/// An app as `tock --zone UTC ARGS...` would start it, without state files.
+fn app(args: &[&str], mode: Mode) -> App {
+    let cli = Cli::try_parse_from(["tock", "--zone", "UTC"].iter().chain(args)).unwrap();
+    App::new(&cli, Config::default(), mode)
+}
+
+fn text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let line: String = (area.left()..area.right())
+            .map(|x| buffer.get(x, y).symbol.as_str())
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn render(app: &App, width: u16, height: u16, source: &dyn TimeSource) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|f| app.draw(f, source.now(), source.instant()))
+        .unwrap();
+    text(terminal.backend().buffer())
+}
+
+fn press(app: &mut App, code: KeyCode, times: usize, term: Rect, source: &dyn TimeSource) {
+    for _ in 0..times {
+        app.key(code, term, source);
+    }
+}
+
+#[track_caller]
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.txt", name));
+    if env::var_os("TOCK_BLESS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "no snapshot {}, run with TOCK_BLESS=1 to create it",
+            path.display()
+        )
+    });
+    if expected != actual {
+        panic!(
+            "snapshot {} changed, run with TOCK_BLESS=1 to accept\n--- expected\n{}--- actual\n{}",
+            path.display(),
+            expected,
+            actual
+        );
+    }
+}
+
+#[test]
+fn clock_default() {
+    let source = clock();
+    let app = app(&[], Mode::Clock);
+    assert_snapshot("clock_default", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn clock_block_marker_without_marks() {
+    let source = clock();
+    let mut app = app(&["--marker", "block", "--size", "60x30"], Mode::Clock);
+    app.dial.style.mark_marker = None;
+    assert_snapshot("clock_block_marker", &render(&app, 60, 30, &source));
+}
+
+#[test]
+fn clock_tiny_terminals() {
+    let source = clock();
+    let app = app(&[], Mode::Clock);
+    for (width, height) in [(1, 1), (4, 2), (12, 6), (30, 8)] {
+        let name = format!("clock_tiny_{}x{}", width, height);
+        assert_snapshot(&name, &render(&app, width, height, &source));
+    }
+}
+
+#[test]
+fn clock_resize_limits() {
+    let source = clock();
+    let term = Rect::new(0, 0, 40, 20);
+    let mut app = app(&["--size", "20x10"], Mode::Clock);
+
+    press(&mut app, KeyCode::Up, 50, term, &source);
+    press(&mut app, KeyCode::Right, 50, term, &source);
+    assert_eq!((app.center_width, app.center_height), (40, 20));
+    assert_snapshot("clock_resize_max", &render(&app, 40, 20, &source));
+
+    press(&mut app, KeyCode::Down, 50, term, &source);
+    press(&mut app, KeyCode::Left, 50, term, &source);
+    assert_eq!((app.center_width, app.center_height), (0, 0));
+    assert_snapshot("clock_resize_min", &render(&app, 40, 20, &source));
+
+    // with the aspect locked the width follows the height
+    press(&mut app, KeyCode::Char('a'), 1, term, &source);
+    press(&mut app, KeyCode::Up, 12, term, &source);
+    assert_eq!((app.center_width, app.center_height), (24, 12));
+    assert_snapshot("clock_resize_locked", &render(&app, 40, 20, &source));
+}
+
+#[test]
+fn world() {
+    let source = clock();
+    let zones = ["UTC", "Asia/Tokyo", "America/New_York", "Asia/Kolkata"]
+        .map(|name| Zone::parse(name).unwrap())
+        .to_vec();
+    let world = WorldClock::new(zones, Zone::Utc);
+    let app = app(&[], Mode::World(world));
+    assert_snapshot("world", &render(&app, 100, 40, &source));
+}
+
+#[test]
+fn stopwatch_with_laps() {
+    let mut source = clock();
+    let mut stopwatch = Stopwatch::new();
+    stopwatch.toggle(source.instant());
+    source.advance(Duration::from_millis(83_400));
+    stopwatch.lap(source.instant());
+    source.advance(Duration::from_millis(20_100));
+    stopwatch.lap(source.instant());
+    source.advance(Duration::from_millis(4_750));
+    let app = app(&[], Mode::Stopwatch(stopwatch));
+    assert_snapshot("stopwatch", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn timer_running_and_paused() {
+    let mut source = clock();
+    let countdown = Countdown::new(Duration::from_secs(25 * 60), source.instant());
+    let mut app = app(&[], Mode::Timer(countdown));
+    source.advance(Duration::from_secs(7 * 60 + 12));
+    assert_snapshot("timer", &render(&app, 80, 40, &source));
+
+    press(
+        &mut app,
+        KeyCode::Char(' '),
+        1,
+        Rect::new(0, 0, 80, 40),
+        &source,
+    );
+    source.advance(Duration::from_secs(60));
+    assert_snapshot("timer_paused", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn pomodoro_second_round() {
+    let mut source = clock();
+    let program = Program::pomodoro(
+        Duration::from_secs(25 * 60),
+        Duration::from_secs(5 * 60),
+        Duration::from_secs(15 * 60),
+        4,
+    );
+    let mut app = app(&[], Mode::Cycle(Cycle::new(program, source.instant())));
+    source.advance(Duration::from_secs(25 * 60));
+    app.tick(&source);
+    source.advance(Duration::from_secs(5 * 60));
+    app.tick(&source);
+    source.advance(Duration::from_secs(10 * 60));
+    assert_snapshot("pomodoro", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn interval() {
+    let mut source = clock();
+    let program = parse_interval("8x20s/10s").unwrap();
+    let app = app(&[], Mode::Cycle(Cycle::new(program, source.instant())));
+    source.advance(Duration::from_secs(5));
+    assert_snapshot("interval", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn alarm_prompt() {
+    let source = clock();
+    let term = Rect::new(0, 0, 80, 40);
+    let mut app = app(&[], Mode::Clock);
+    press(&mut app, KeyCode::Char('@'), 1, term, &source);
+    for c in "07:3x".chars() {
+        press(&mut app, KeyCode::Char(c), 1, term, &source);
+    }
+    press(&mut app, KeyCode::Enter, 1, term, &source);
+    assert_snapshot("alarm_prompt", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn alarm_ringing() {
+    let source = clock();
+    let mut app = app(&[], Mode::Clock);
+    app.alarms
+        .add(Alarm::parse("10:08 weekends Coffee").unwrap())
+        .unwrap();
+    assert!(app.tick(&source), "the bell rings when the alarm goes off");
+    assert_snapshot("alarm_ringing", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn config_error() {
+    let source = clock();
+    let mut app = app(&[], Mode::Clock);
+    app.config_error = Some(
+        "config.toml: TOML parse error at line 3, column 8\nunknown field `colour`".to_string(),
+    );
+    assert_snapshot("config_error", &render(&app, 80, 40, &source));
+}
+
+#[test]
+fn clock_complications() {
+    let source = clock();
+    let term = Rect::new(0, 0, 80, 40);
+    let mut app = app(&[], Mode::Clock);
+    for c in "12345".chars() {
+        press(&mut app, KeyCode::Char(c), 1, term, &source);
+    }
+    assert_snapshot("clock_complications", &render(&app, 80, 40, &source));
+
+    // a second press hides it again
+    press(&mut app, KeyCode::Char('1'), 1, term, &source);
+    assert!(!app.complications.is_shown(Complication::Digital));
+}
+
+#[test]
+fn faces() {
+    let source = clock();
+    let term = Rect::new(0, 0, 60, 30);
+    let mut app = app(&["--size", "60x30"], Mode::Clock);
+    for name in ["dots", "arabic", "roman", "ticks", "24h", "plain"] {
+        assert_snapshot(&format!("face_{}", name), &render(&app, 60, 30, &source));
+        press(&mut app, KeyCode::Char('f'), 1, term, &source);
+    }
+    assert_eq!(app.dial.style.face, Face::Dots, "the key cycles back round");
+}
+
+#[test]
+fn hand_shapes() {
+    let source = clock();
+    let args = [
+        "--size",
+        "60x30",
+        "--hour-shape",
+        "tapered",
+        "--minute-shape",
+        "arrow",
+        "--second-shape",
+        "counterweight",
+    ];
+    let app = app(&args, Mode::Clock);
+    assert_snapshot("hand_shapes", &render(&app, 60, 30, &source));
+}
+
+#[test]
+fn help_stopwatch() {
+    let source = clock();
+    let term = Rect::new(0, 0, 80, 40);
+    let mut app = app(&[], Mode::Clock);
+    press(&mut app, KeyCode::Char('m'), 1, term, &source);
+    press(&mut app, KeyCode::Char('?'), 1, term, &source);
+    assert_snapshot("help_stopwatch", &render(&app, 80, 40, &source));
+
+    // keys still work under the help, esc closes it
+    press(&mut app, KeyCode::Char(' '), 1, term, &source);
+    assert!(app.stopwatch().unwrap().is_running());
+    press(&mut app, KeyCode::Esc, 1, term, &source);
+    assert!(!app.help);
+    press(&mut app, KeyCode::Char('m'), 1, term, &source);
+    assert!(matches!(app.mode, Mode::Clock));
+}
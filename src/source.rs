@@ -0,0 +1,189 @@
+// ====================================
+// ===== Time sources
+// ====================================
+
+use std::time::{Duration, Instant};
+
+use time::OffsetDateTime;
+
+/// Where tock reads the time from. `now` is the wall clock the dial shows,
+/// `instant` the monotonic clock stopwatches and timers run on.
+pub trait TimeSource {
+    fn now(&self) -> OffsetDateTime;
+    fn instant(&self) -> Instant;
+
+    /// Seconds this source advances per real second, so callers know how
+    /// long to sleep until it reaches some point; 0 if it stands still.
+    fn speed(&self) -> f64 {
+        1.0
+    }
+}
+
+/// The real clocks of the machine.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Stands still until moved by hand, for tests and screenshots.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock {
+    now: OffsetDateTime,
+    instant: Instant,
+}
+
+impl FixedClock {
+    pub fn new(now: OffsetDateTime) -> FixedClock {
+        FixedClock {
+            now,
+            instant: Instant::now(),
+        }
+    }
+
+    /// Jumps the wall clock; the monotonic clock is left alone.
+    pub fn set(&mut self, now: OffsetDateTime) {
+        self.now = now;
+    }
+
+    /// Moves both clocks forward.
+    pub fn advance(&mut self, by: Duration) {
+        self.now += by;
+        self.instant += by;
+    }
+}
+
+impl TimeSource for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        self.now
+    }
+
+    fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    fn speed(&self) -> f64 {
+        0.0
+    }
+}
+
+/// Another source shifted by `offset` and running `speed` times as fast,
+/// counted from the moment it was created. Stops once a century of scaled
+/// time has passed, or at the last moment `OffsetDateTime` can hold.
+#[derive(Debug, Clone, Copy)]
+pub struct ScaledClock<S = SystemClock> {
+    inner: S,
+    start: OffsetDateTime,
+    start_instant: Instant,
+    offset: time::Duration,
+    speed: f64,
+}
+
+impl<S: TimeSource> ScaledClock<S> {
+    /// How far the scaled clocks run from the start.
+    const MAX_ELAPSED: Duration = Duration::from_secs(100 * 365 * 24 * 3600);
+
+    /// `speed` must be finite and not negative; monotonic time cannot run
+    /// backwards.
+    pub fn new(inner: S, offset: time::Duration, speed: f64) -> ScaledClock<S> {
+        assert!(
+            speed.is_finite() && speed >= 0.0,
+            "speed must be finite and not negative, got {}",
+            speed
+        );
+        ScaledClock {
+            start: inner.now(),
+            start_instant: inner.instant(),
+            inner,
+            offset,
+            speed,
+        }
+    }
+}
+
+impl<S: TimeSource> TimeSource for ScaledClock<S> {
+    fn now(&self) -> OffsetDateTime {
+        let max = Self::MAX_ELAPSED.as_secs_f64();
+        let elapsed = (self.inner.now() - self.start).as_seconds_f64() * self.speed;
+        let elapsed = time::Duration::seconds_f64(elapsed.clamp(-max, max));
+        self.start
+            .saturating_add(self.offset.saturating_add(elapsed))
+    }
+
+    fn instant(&self) -> Instant {
+        let elapsed = self
+            .inner
+            .instant()
+            .saturating_duration_since(self.start_instant);
+        let elapsed = elapsed.as_secs_f64() * self.speed;
+        let elapsed = Duration::from_secs_f64(elapsed.min(Self::MAX_ELAPSED.as_secs_f64()));
+        self.start_instant
+            .checked_add(elapsed)
+            .unwrap_or(self.start_instant)
+    }
+
+    fn speed(&self) -> f64 {
+        self.speed * self.inner.speed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn fixed_clock_moves_only_by_hand() {
+        let start = datetime!(2024-03-10 10:08:37 UTC);
+        let mut clock = FixedClock::new(start);
+        let instant = clock.instant();
+        assert_eq!((clock.now(), clock.instant()), (start, instant));
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now(), datetime!(2024-03-10 10:10:07 UTC));
+        assert_eq!(clock.instant(), instant + Duration::from_secs(90));
+
+        clock.set(start);
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.instant(), instant + Duration::from_secs(90));
+    }
+
+    #[test]
+    fn scaled_clock_shifts_and_speeds_up() {
+        let start = datetime!(2024-03-10 10:00:00 UTC);
+        let mut inner = FixedClock::new(start);
+        let instant = inner.instant();
+        let scaled = ScaledClock::new(inner, time::Duration::hours(-2), 60.0);
+        assert_eq!(scaled.now(), datetime!(2024-03-10 08:00:00 UTC));
+        assert_eq!(scaled.speed(), 0.0, "it runs on a clock that stands still");
+
+        inner.advance(Duration::from_secs(10));
+        let scaled = ScaledClock { inner, ..scaled };
+        assert_eq!(scaled.now(), datetime!(2024-03-10 08:10:00 UTC));
+        assert_eq!(scaled.instant(), instant + Duration::from_secs(600));
+    }
+
+    #[test]
+    fn scaled_clock_stops_instead_of_overflowing() {
+        let start = datetime!(2024-03-10 10:00:00 UTC);
+        let mut inner = FixedClock::new(start);
+        let instant = inner.instant();
+        let scaled = ScaledClock::new(inner, time::Duration::ZERO, 1e30);
+        inner.advance(Duration::from_secs(3600));
+        let scaled = ScaledClock { inner, ..scaled };
+        let century = ScaledClock::<FixedClock>::MAX_ELAPSED;
+        assert_eq!(scaled.now(), start + century);
+        assert_eq!(scaled.instant(), instant + century);
+
+        let offset = time::Duration::MAX;
+        let scaled = ScaledClock::new(inner, offset, 1.0);
+        assert!(scaled.now() > start);
+    }
+}
@@ -0,0 +1,220 @@
+// ====================================
+// ===== Stopwatch
+// ====================================
+
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    widgets::{Block, Borders, Cell, GraphType, Row, Table},
+    Frame,
+};
+
+use tock::dial::{dial_rot, Dial, Hands, Layer, Motion};
+use tock::theme::Theme;
+use tock::ClockWidget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Lap {
+    pub number: usize,
+    /// Time since the previous lap.
+    #[serde(rename = "split_seconds", serialize_with = "ser_secs")]
+    pub split: Duration,
+    /// Time since the stopwatch started.
+    #[serde(rename = "total_seconds", serialize_with = "ser_secs")]
+    pub total: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// `.json` files get JSON, everything else CSV.
+    pub fn from_path(path: &Path) -> ExportFormat {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        }
+    }
+}
+
+/// Start/stop/lap bookkeeping; every method takes `now` so the caller owns
+/// the clock.
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    running_since: Option<Instant>,
+    accumulated: Duration,
+    laps: Vec<Lap>,
+}
+
+impl Stopwatch {
+    pub fn new() -> Stopwatch {
+        Stopwatch::default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        match self.running_since {
+            Some(since) => self.accumulated + now.saturating_duration_since(since),
+            None => self.accumulated,
+        }
+    }
+
+    pub fn toggle(&mut self, now: Instant) {
+        match self.running_since.take() {
+            Some(since) => self.accumulated += now.saturating_duration_since(since),
+            None => self.running_since = Some(now),
+        }
+    }
+
+    /// Records a lap; ignored while stopped at zero.
+    pub fn lap(&mut self, now: Instant) {
+        let total = self.elapsed(now);
+        if total.is_zero() {
+            return;
+        }
+        let previous = self.laps.last().map(|lap| lap.total).unwrap_or_default();
+        self.laps.push(Lap {
+            number: self.laps.len() + 1,
+            split: total - previous,
+            total,
+        });
+    }
+
+    pub fn reset(&mut self) {
+        *self = Stopwatch::new();
+    }
+
+    pub fn export(&self, path: &Path, format: ExportFormat) -> io::Result<()> {
+        let text = match format {
+            ExportFormat::Csv => {
+                let mut csv = String::from("lap,split_seconds,total_seconds\n");
+                for lap in &self.laps {
+                    csv.push_str(&format!(
+                        "{},{:.3},{:.3}\n",
+                        lap.number,
+                        lap.split.as_secs_f64(),
+                        lap.total.as_secs_f64()
+                    ));
+                }
+                csv
+            }
+            ExportFormat::Json => serde_json::to_string_pretty(&self.laps)?,
+        };
+        fs::write(path, text)
+    }
+
+    pub fn render<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        dial: &Dial,
+        now: Instant,
+        motion: Motion,
+        theme: &Theme,
+    ) {
+        let elapsed = self.elapsed(now);
+        let state = if self.is_running() {
+            "running"
+        } else {
+            "stopped"
+        };
+        let title = format!("Stopwatch {} ({})", format_duration(elapsed), state);
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let parts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(inner);
+
+        let seconds = elapsed.as_secs_f64();
+        let layers = tenths_dial(seconds, dial, theme);
+        let clock = ClockWidget::new()
+            .dial(dial)
+            .hands(Hands::from_seconds(motion.quantize(seconds)))
+            .layers(&layers);
+        f.render_widget(clock, parts[0]);
+
+        let header =
+            Row::new(["#", "Lap", "Total"]).style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.laps.iter().rev().map(|lap| {
+            Row::new([
+                Cell::from(lap.number.to_string()),
+                Cell::from(format_duration(lap.split)),
+                Cell::from(format_duration(lap.total)),
+            ])
+        });
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().title("Laps").borders(Borders::LEFT))
+            .widths(&[
+                Constraint::Length(4),
+                Constraint::Length(11),
+                Constraint::Length(11),
+            ]);
+        f.render_widget(table, parts[1]);
+    }
+}
+
+/// Small dial below the centre whose hand goes round once per second,
+/// with a mark for every tenth.
+fn tenths_dial(seconds: f64, dial: &Dial, theme: &Theme) -> Vec<Layer> {
+    let center = dial_rot(0.5) * 0.4;
+    let radius = 0.2;
+    let ticks = (0..10)
+        .map(|i| (center + dial_rot(i as f64 / 10.0) * radius).into())
+        .collect();
+    let tenths = (seconds * 10.0).floor() / 10.0;
+    let tip = center + dial_rot(tenths.fract()) * (radius * 0.8);
+    vec![
+        Layer {
+            points: ticks,
+            graph: GraphType::Scatter,
+            marker: Marker::Braille,
+            color: dial.style.marks,
+        },
+        Layer {
+            points: vec![center.into(), tip.into()],
+            graph: GraphType::Line,
+            marker: Marker::Braille,
+            color: theme.accent,
+        },
+    ]
+}
+
+/// `05:07.3`, or `1:05:07.3` once an hour has passed.
+pub fn format_duration(d: Duration) -> String {
+    let tenths = d.as_millis() / 100;
+    let (h, m, s, t) = (
+        tenths / 36000,
+        tenths / 600 % 60,
+        tenths / 10 % 60,
+        tenths % 10,
+    );
+    if h > 0 {
+        format!("{}:{:02}:{:02}.{}", h, m, s, t)
+    } else {
+        format!("{:02}:{:02}.{}", m, s, t)
+    }
+}
+
+fn ser_secs<S: serde::Serializer>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64((d.as_secs_f64() * 1000.0).round() / 1000.0)
+}
@@ -0,0 +1,155 @@
+// ====================================
+// ===== Terminal setup and restore
+// ====================================
+
+use std::{
+    io::{self, Stdout},
+    ops::{Deref, DerefMut},
+    panic,
+    sync::Once,
+};
+
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tui::{backend::CrosstermBackend, Terminal};
+
+/// The terminal in raw mode on the alternate screen. Dropping it, or a
+/// panic anywhere, puts the terminal back the way it was.
+pub struct TerminalGuard {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+}
+
+impl TerminalGuard {
+    pub fn new() -> io::Result<TerminalGuard> {
+        install_panic_hook();
+        enter()?;
+        let terminal = match Terminal::new(CrosstermBackend::new(io::stdout())) {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                let _ = leave();
+                return Err(e);
+            }
+        };
+        Ok(TerminalGuard { terminal })
+    }
+
+    /// Restores the terminal and stops the process like Ctrl-Z normally
+    /// would; once the shell resumes it, takes the terminal back and clears
+    /// it so the next frame is drawn in full.
+    pub fn suspend(&mut self) -> io::Result<()> {
+        leave()?;
+        #[cfg(unix)]
+        signal_hook::low_level::raise(signal_hook::consts::SIGSTOP)?;
+        enter()?;
+        self.terminal.clear()
+    }
+}
+
+impl Deref for TerminalGuard {
+    type Target = Terminal<CrosstermBackend<Stdout>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl DerefMut for TerminalGuard {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = leave();
+    }
+}
+
+fn enter() -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+}
+
+fn leave() -> io::Result<()> {
+    let raw = disable_raw_mode();
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )?;
+    raw
+}
+
+/// Restores the terminal before the panic message is printed, so it ends up
+/// on the normal screen instead of vanishing with the alternate one.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = leave();
+            hook(info);
+        }));
+    });
+}
+
+/// SIGTERM, SIGINT, SIGHUP and SIGTSTP, noted by the handlers and acted on
+/// by the main loop so the terminal is only touched from one thread.
+pub struct Signals {
+    #[cfg(unix)]
+    terminate: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    #[cfg(unix)]
+    suspend: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(unix)]
+impl Signals {
+    pub fn new() -> io::Result<Signals> {
+        use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM, SIGTSTP};
+        use signal_hook::flag;
+
+        let signals = Signals {
+            terminate: Default::default(),
+            suspend: Default::default(),
+        };
+        for signal in [SIGTERM, SIGINT, SIGHUP] {
+            flag::register_usize(signal, signals.terminate.clone(), signal as usize)?;
+        }
+        flag::register(SIGTSTP, signals.suspend.clone())?;
+        Ok(signals)
+    }
+
+    /// The number of a terminating signal that arrived, if any.
+    pub fn terminated(&self) -> Option<i32> {
+        match self.terminate.load(std::sync::atomic::Ordering::Relaxed) {
+            0 => None,
+            signal => Some(signal as i32),
+        }
+    }
+
+    /// True once for every SIGTSTP, e.g. from `kill -TSTP`.
+    pub fn take_suspend(&self) -> bool {
+        self.suspend
+            .swap(false, std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[cfg(not(unix))]
+impl Signals {
+    pub fn new() -> io::Result<Signals> {
+        Ok(Signals {})
+    }
+
+    pub fn terminated(&self) -> Option<i32> {
+        None
+    }
+
+    pub fn take_suspend(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,339 @@
+// ====================================
+// ===== Colour themes
+// ====================================
+
+use tui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+
+use crate::dial::DialStyle;
+
+/// Every colour tock draws with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub hour: Color,
+    pub minute: Color,
+    pub second: Color,
+    pub marks: Color,
+    /// When set, the marks shade from `marks` at 12 o'clock round to this.
+    pub marks_to: Option<Color>,
+    /// The timer's arc, shading to `arc_to` towards the full hour.
+    pub arc: Color,
+    pub arc_to: Option<Color>,
+    pub work: Color,
+    pub short_break: Color,
+    pub long_break: Color,
+    /// Laps and other highlights.
+    pub accent: Color,
+    pub error: Color,
+    pub notice: Color,
+    /// Hints and finished phases.
+    pub dim: Color,
+    /// Background of a ringing alarm.
+    pub flash: Color,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        hour: Color::Red,
+        minute: Color::Green,
+        second: Color::Blue,
+        marks: Color::White,
+        marks_to: None,
+        arc: Color::Red,
+        arc_to: None,
+        work: Color::Red,
+        short_break: Color::Green,
+        long_break: Color::Blue,
+        accent: Color::Yellow,
+        error: Color::Red,
+        notice: Color::Yellow,
+        dim: Color::DarkGray,
+        flash: Color::Red,
+    };
+
+    /// For terminals with a light background.
+    pub const LIGHT: Theme = Theme {
+        hour: Color::Rgb(0xb0, 0x10, 0x10),
+        minute: Color::Rgb(0x10, 0x70, 0x20),
+        second: Color::Rgb(0x10, 0x40, 0xb0),
+        marks: Color::Black,
+        marks_to: None,
+        arc: Color::Rgb(0xd0, 0x40, 0x20),
+        arc_to: Some(Color::Rgb(0x80, 0x10, 0x60)),
+        work: Color::Rgb(0xb0, 0x10, 0x10),
+        short_break: Color::Rgb(0x10, 0x70, 0x20),
+        long_break: Color::Rgb(0x10, 0x40, 0xb0),
+        accent: Color::Rgb(0x90, 0x60, 0x00),
+        error: Color::Rgb(0xb0, 0x10, 0x10),
+        notice: Color::Rgb(0x90, 0x60, 0x00),
+        dim: Color::Gray,
+        flash: Color::Rgb(0xff, 0xa0, 0xa0),
+    };
+
+    /// Ethan Schoonover's Solarized accents.
+    pub const SOLARIZED: Theme = Theme {
+        hour: Color::Rgb(0xdc, 0x32, 0x2f),
+        minute: Color::Rgb(0x85, 0x99, 0x00),
+        second: Color::Rgb(0x26, 0x8b, 0xd2),
+        marks: Color::Rgb(0x83, 0x94, 0x96),
+        marks_to: Some(Color::Rgb(0x2a, 0xa1, 0x98)),
+        arc: Color::Rgb(0xcb, 0x4b, 0x16),
+        arc_to: Some(Color::Rgb(0xd3, 0x36, 0x82)),
+        work: Color::Rgb(0xdc, 0x32, 0x2f),
+        short_break: Color::Rgb(0x85, 0x99, 0x00),
+        long_break: Color::Rgb(0x6c, 0x71, 0xc4),
+        accent: Color::Rgb(0xb5, 0x89, 0x00),
+        error: Color::Rgb(0xdc, 0x32, 0x2f),
+        notice: Color::Rgb(0xb5, 0x89, 0x00),
+        dim: Color::Rgb(0x58, 0x6e, 0x75),
+        flash: Color::Rgb(0xdc, 0x32, 0x2f),
+    };
+
+    /// Bright colours only, nothing dimmed.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        hour: Color::LightRed,
+        minute: Color::LightGreen,
+        second: Color::LightCyan,
+        marks: Color::White,
+        marks_to: None,
+        arc: Color::LightYellow,
+        arc_to: None,
+        work: Color::LightRed,
+        short_break: Color::LightGreen,
+        long_break: Color::LightCyan,
+        accent: Color::LightYellow,
+        error: Color::LightRed,
+        notice: Color::LightYellow,
+        dim: Color::Gray,
+        flash: Color::LightRed,
+    };
+
+    pub const MONOCHROME: Theme = Theme {
+        hour: Color::White,
+        minute: Color::Gray,
+        second: Color::DarkGray,
+        marks: Color::White,
+        marks_to: None,
+        arc: Color::Gray,
+        arc_to: None,
+        work: Color::White,
+        short_break: Color::Gray,
+        long_break: Color::Gray,
+        accent: Color::White,
+        error: Color::White,
+        notice: Color::White,
+        dim: Color::DarkGray,
+        flash: Color::Gray,
+    };
+
+    pub const NAMES: [&'static str; 5] =
+        ["dark", "light", "solarized", "high-contrast", "monochrome"];
+
+    /// One of `NAMES`.
+    pub fn builtin(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::DARK),
+            "light" => Some(Theme::LIGHT),
+            "solarized" => Some(Theme::SOLARIZED),
+            "high-contrast" => Some(Theme::HIGH_CONTRAST),
+            "monochrome" => Some(Theme::MONOCHROME),
+            _ => None,
+        }
+    }
+
+    /// `base` with the dial colours of this theme.
+    pub fn style(&self, base: DialStyle) -> DialStyle {
+        DialStyle {
+            hour: self.hour,
+            minute: self.minute,
+            second: self.second,
+            marks: self.marks,
+            marks_to: self.marks_to,
+            ..base
+        }
+    }
+
+    /// Colour `t` of the way along the arc, 0.0 at 12 o'clock.
+    pub fn arc_at(&self, t: f64) -> Color {
+        match self.arc_to {
+            Some(to) => blend(self.arc, to, t),
+            None => self.arc,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DARK
+    }
+}
+
+/// How many colours the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Guesses from `$COLORTERM` and `$TERM`, like most terminal programs.
+    pub fn detect() -> ColorDepth {
+        let var = |name| std::env::var(name).unwrap_or_default().to_ascii_lowercase();
+        let colorterm = var("COLORTERM");
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if var("TERM").contains("256") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// The closest colour this depth can show.
+    pub fn fit(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(..) | Color::Indexed(_)) => match rgb(color) {
+                Some((r, g, b)) => nearest_16(r, g, b),
+                None => color,
+            },
+            _ => color,
+        }
+    }
+}
+
+/// Rewrites every colour in the area it is rendered into to one `0` can
+/// show; drawn last, over the whole frame.
+pub struct Fallback(pub ColorDepth);
+
+impl Widget for Fallback {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.0 == ColorDepth::TrueColor {
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                cell.fg = self.0.fit(cell.fg);
+                cell.bg = self.0.fit(cell.bg);
+            }
+        }
+    }
+}
+
+/// The 16 named colours as xterm shows them.
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of each channel in the 6x6x6 cube of the 256 colour palette.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Red, green and blue of `color`; `None` for `Reset`.
+pub fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(i @ 0..=15) => Some(ANSI[i as usize].1),
+        Color::Indexed(i @ 16..=231) => {
+            let i = i - 16;
+            Some((
+                CUBE[i as usize / 36],
+                CUBE[i as usize / 6 % 6],
+                CUBE[i as usize % 6],
+            ))
+        }
+        Color::Indexed(i) => {
+            let level = 8 + 10 * (i - 232);
+            Some((level, level, level))
+        }
+        named => ANSI.iter().find(|(c, _)| *c == named).map(|&(_, rgb)| rgb),
+    }
+}
+
+/// `t` of the way from `from` to `to`, in RGB. Colours without RGB values
+/// switch over halfway.
+pub fn blend(from: Color, to: Color, t: f64) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    match (rgb(from), rgb(to)) {
+        (Some(a), Some(b)) => {
+            let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+            Color::Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI.iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|&(color, _)| color)
+        .unwrap_or(Color::Reset)
+}
+
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&i| (CUBE[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = 16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8;
+    let grey_level = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+    let grey = 232 + (grey_level.saturating_sub(3) / 10).min(23);
+    let candidates = [cube, grey];
+    candidates
+        .into_iter()
+        .min_by_key(|&i| distance(rgb(Color::Indexed(i)).unwrap_or_default(), (r, g, b)))
+        .unwrap_or(cube)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_picks_the_nearest_colour() {
+        let orange = Color::Rgb(0xcb, 0x4b, 0x16);
+        assert_eq!(ColorDepth::TrueColor.fit(orange), orange);
+        assert_eq!(ColorDepth::Ansi256.fit(orange), Color::Indexed(166));
+        assert_eq!(ColorDepth::Ansi16.fit(orange), Color::Red);
+        assert_eq!(
+            ColorDepth::Ansi256.fit(Color::Rgb(0x58, 0x6e, 0x75)),
+            Color::Indexed(242)
+        );
+        // named colours are already as small as it gets
+        assert_eq!(ColorDepth::Ansi16.fit(Color::LightBlue), Color::LightBlue);
+        assert_eq!(ColorDepth::Ansi16.fit(Color::Indexed(196)), Color::LightRed);
+    }
+
+    #[test]
+    fn blend_goes_through_rgb() {
+        let (black, white) = (Color::Black, Color::White);
+        assert_eq!(blend(black, white, 0.0), Color::Rgb(0, 0, 0));
+        assert_eq!(blend(black, white, 0.5), Color::Rgb(128, 128, 128));
+        assert_eq!(blend(Color::Reset, white, 0.4), Color::Reset);
+    }
+}
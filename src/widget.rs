@@ -0,0 +1,152 @@
+// ====================================
+// ===== Clock widget
+// ====================================
+
+use std::borrow::Cow;
+
+use time::OffsetDateTime;
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Widget},
+};
+
+use crate::complication::Complications;
+use crate::dial::{Dial, DialStyle, HandSet, Hands, Layer, Motion};
+use crate::source::{SystemClock, TimeSource};
+use crate::zone::Zone;
+
+/// An analog clock that renders into any `Rect`.
+///
+/// ```
+/// use tock::{ClockWidget, HandSet, Zone};
+/// use tui::{buffer::Buffer, layout::Rect, widgets::Widget};
+///
+/// let area = Rect::new(0, 0, 40, 20);
+/// let mut buf = Buffer::empty(area);
+/// ClockWidget::new()
+///     .zone(Zone::parse("Europe/Berlin").unwrap())
+///     .hand_set(HandSet::NO_SECONDS)
+///     .render(area, &mut buf);
+/// ```
+///
+/// Without `time` it shows the current time; without `zone` the system
+/// zone. `hands` replaces the time entirely, e.g. to show elapsed time.
+#[derive(Debug, Clone)]
+pub struct ClockWidget<'a> {
+    dial: Cow<'a, Dial>,
+    time: Option<OffsetDateTime>,
+    zone: Option<Zone>,
+    hands: Option<Hands>,
+    hand_set: HandSet,
+    motion: Motion,
+    layers: &'a [Layer],
+    complications: Complications,
+    block: Option<Block<'a>>,
+}
+
+impl<'a> ClockWidget<'a> {
+    pub fn new() -> ClockWidget<'a> {
+        ClockWidget {
+            dial: Cow::Owned(Dial::default()),
+            time: None,
+            zone: None,
+            hands: None,
+            hand_set: HandSet::ALL,
+            motion: Motion::Tick,
+            layers: &[],
+            complications: Complications::new(),
+            block: None,
+        }
+    }
+
+    /// The instant to show, converted into `zone`.
+    pub fn time(mut self, time: OffsetDateTime) -> ClockWidget<'a> {
+        self.time = Some(time);
+        self
+    }
+
+    pub fn zone(mut self, zone: Zone) -> ClockWidget<'a> {
+        self.zone = Some(zone);
+        self
+    }
+
+    /// Explicit hand positions, ignoring `time` and `zone`.
+    pub fn hands(mut self, hands: Hands) -> ClockWidget<'a> {
+        self.hands = Some(hands);
+        self
+    }
+
+    pub fn hand_set(mut self, hand_set: HandSet) -> ClockWidget<'a> {
+        self.hand_set = hand_set;
+        self
+    }
+
+    pub fn motion(mut self, motion: Motion) -> ClockWidget<'a> {
+        self.motion = motion;
+        self
+    }
+
+    pub fn style(mut self, style: DialStyle) -> ClockWidget<'a> {
+        self.dial = Cow::Owned(Dial::new(style));
+        self
+    }
+
+    /// Reuses a prepared dial instead of building one from a style.
+    pub fn dial(mut self, dial: &'a Dial) -> ClockWidget<'a> {
+        self.dial = Cow::Borrowed(dial);
+        self
+    }
+
+    /// Drawn between the hour marks and the hands.
+    pub fn layers(mut self, layers: &'a [Layer]) -> ClockWidget<'a> {
+        self.layers = layers;
+        self
+    }
+
+    /// Readouts drawn over the dial. They follow `time` and `zone`, so they
+    /// are left out when only `hands` is given.
+    pub fn complications(mut self, complications: Complications) -> ClockWidget<'a> {
+        self.complications = complications;
+        self
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> ClockWidget<'a> {
+        self.block = Some(block);
+        self
+    }
+}
+
+impl Default for ClockWidget<'_> {
+    fn default() -> Self {
+        ClockWidget::new()
+    }
+}
+
+impl Widget for ClockWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        let time = match self.hands {
+            Some(_) => self.time,
+            None => Some(self.time.unwrap_or_else(|| SystemClock.now())),
+        };
+        let local = time.map(|time| self.zone.unwrap_or_else(Zone::local).at(time));
+        let hands = self
+            .hands
+            .or_else(|| local.map(|local| self.dial.hands(local, self.motion)));
+        self.dial
+            .draw(area, buf, hands.as_ref(), self.hand_set, self.layers);
+        if let Some(local) = local {
+            let style = Style::default().fg(self.dial.style.marks);
+            self.complications.draw(area, buf, local, style);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// ====================================
+// ===== World clock grid
+// ====================================
+
+use time::OffsetDateTime;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Block, Borders},
+    Frame,
+};
+
+use tock::complication::Complications;
+use tock::dial::{Dial, Motion};
+use tock::zone::{format_offset, Zone};
+use tock::ClockWidget;
+
+/// Splits `area` into `n` cells, picking the column count that gives the
+/// biggest round dial (terminal cells are about twice as tall as wide).
+pub fn grid(area: Rect, n: usize) -> Vec<Rect> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let (cols, rows) = (1..=n)
+        .map(|cols| (cols, n.div_ceil(cols)))
+        .max_by_key(|&(cols, rows)| {
+            let w = area.width as usize / cols;
+            let h = area.height as usize / rows;
+            // biggest dial first, then the fewest empty cells
+            ((w / 2).min(h), std::cmp::Reverse(cols * rows - n))
+        })
+        .unwrap_or((1, n));
+
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+        .split(area);
+
+    row_areas
+        .into_iter()
+        .flat_map(|row| {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, cols as u32); cols])
+                .split(row)
+        })
+        .take(n)
+        .collect()
+}
+
+/// `Tokyo +09:00 Wed` - the weekday only shows up when it differs from `local`.
+pub fn caption(zone: &Zone, now: OffsetDateTime, local: &Zone) -> String {
+    let there = zone.at(now);
+    let mut caption = format!("{} {}", zone.city(), format_offset(there.offset()));
+    if there.weekday() != local.at(now).weekday() {
+        caption.push(' ');
+        caption.push_str(&there.weekday().to_string()[..3]);
+    }
+    caption
+}
+
+pub struct WorldClock {
+    pub zones: Vec<Zone>,
+    /// The zone tock shows elsewhere, which the captions' weekdays are
+    /// relative to.
+    pub local: Zone,
+}
+
+impl WorldClock {
+    pub fn new(zones: Vec<Zone>, local: Zone) -> WorldClock {
+        WorldClock { zones, local }
+    }
+
+    pub fn render<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        dial: &Dial,
+        now: OffsetDateTime,
+        motion: Motion,
+        complications: Complications,
+    ) {
+        for (zone, cell) in self.zones.iter().zip(grid(area, self.zones.len())) {
+            let title = caption(zone, now, &self.local);
+            let clock = ClockWidget::new()
+                .dial(dial)
+                .time(now)
+                .zone(*zone)
+                .motion(motion)
+                .complications(complications)
+                .block(Block::default().title(title).borders(Borders::ALL));
+            f.render_widget(clock, cell);
+        }
+    }
+}
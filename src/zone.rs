@@ -0,0 +1,222 @@
+// ====================================
+// ===== Time zones
+// ====================================
+
+use std::{fmt, sync::OnceLock};
+
+use time::{OffsetDateTime, UtcOffset};
+use time_tz::{timezones, OffsetDateTimeExt, TimeZone, Tz};
+
+/// Where the displayed wall-clock time comes from.
+///
+/// Named zones are resolved against the tz database bundled with `time-tz`,
+/// so DST transitions are looked up per instant rather than baked in.
+#[derive(Debug, Clone, Copy)]
+pub enum Zone {
+    Utc,
+    Fixed(UtcOffset),
+    Named(&'static Tz),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownZone(pub String);
+
+impl fmt::Display for UnknownZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown time zone '{}' (expected an IANA name like Europe/Berlin, UTC or an offset like +05:30)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownZone {}
+
+impl Zone {
+    /// The zone of the machine tock runs on: `$TZ` if it names a known zone,
+    /// then `/etc/localtime`, falling back to UTC. Looked up once per process.
+    pub fn local() -> Zone {
+        static LOCAL: OnceLock<Zone> = OnceLock::new();
+        *LOCAL.get_or_init(|| {
+            if let Ok(name) = std::env::var("TZ") {
+                if let Ok(zone) = Zone::parse(name.trim_start_matches(':')) {
+                    return zone;
+                }
+            }
+            match time_tz::system::get_timezone() {
+                Ok(tz) => Zone::Named(tz),
+                Err(_) => Zone::Utc,
+            }
+        })
+    }
+
+    /// Parses an IANA name (`America/New_York`), `UTC`/`Z`, or a fixed
+    /// offset (`+1`, `-03:30`, `+0545`).
+    pub fn parse(name: &str) -> Result<Zone, UnknownZone> {
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("utc") || name.eq_ignore_ascii_case("z") {
+            return Ok(Zone::Utc);
+        }
+        if let Some(tz) = timezones::get_by_name(name) {
+            return Ok(Zone::Named(tz));
+        }
+        parse_offset(name)
+            .map(Zone::Fixed)
+            .ok_or_else(|| UnknownZone(name.to_string()))
+    }
+
+    /// Converts an instant into this zone's wall-clock time.
+    pub fn at(&self, instant: OffsetDateTime) -> OffsetDateTime {
+        match self {
+            Zone::Utc => instant.to_offset(UtcOffset::UTC),
+            Zone::Fixed(offset) => instant.to_offset(*offset),
+            Zone::Named(tz) => instant.to_timezone(*tz),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Zone::Utc => "UTC".to_string(),
+            Zone::Fixed(offset) => format_offset(*offset),
+            Zone::Named(tz) => tz.name().to_string(),
+        }
+    }
+
+    /// Short human label: `New York` for `America/New_York`.
+    pub fn city(&self) -> String {
+        match self {
+            Zone::Named(tz) => {
+                let name = tz.name();
+                name.rsplit('/').next().unwrap_or(name).replace('_', " ")
+            }
+            _ => self.name(),
+        }
+    }
+}
+
+impl Default for Zone {
+    fn default() -> Self {
+        Zone::local()
+    }
+}
+
+/// `+01:00`, `-03:30`, ...
+pub fn format_offset(offset: UtcOffset) -> String {
+    let (h, m, _) = offset.as_hms();
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    format!("{}{:02}:{:02}", sign, h.abs(), m.abs())
+}
+
+fn parse_offset(s: &str) -> Option<UtcOffset> {
+    let s = s
+        .strip_prefix("UTC")
+        .or_else(|| s.strip_prefix("GMT"))
+        .unwrap_or(s);
+    let (sign, rest) = match s.chars().next()? {
+        '+' => (1, &s[1..]),
+        '-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    // digits only: no second sign, and the byte split below stays on a char boundary
+    if !rest.bytes().all(|b| b.is_ascii_digit() || b == b':') {
+        return None;
+    }
+    let (h, m) = match rest.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let h: i8 = h.parse().ok()?;
+    let m: i8 = m.parse().ok()?;
+    if h > 14 || m > 59 {
+        return None;
+    }
+    UtcOffset::from_hms(sign * h, sign * m, 0).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn hour_in(zone: &str, instant: OffsetDateTime) -> u8 {
+        Zone::parse(zone).unwrap().at(instant).hour()
+    }
+
+    #[test]
+    fn berlin_spring_forward() {
+        // 2023-03-26 01:00 UTC: clocks in Berlin jump from 02:00 CET to 03:00 CEST
+        assert_eq!(
+            hour_in("Europe/Berlin", datetime!(2023-03-26 00:59:59 UTC)),
+            1
+        );
+        assert_eq!(
+            hour_in("Europe/Berlin", datetime!(2023-03-26 01:00:00 UTC)),
+            3
+        );
+    }
+
+    #[test]
+    fn berlin_fall_back() {
+        // 2023-10-29 01:00 UTC: 03:00 CEST becomes 02:00 CET, so 02:xx happens twice
+        assert_eq!(
+            hour_in("Europe/Berlin", datetime!(2023-10-29 00:30:00 UTC)),
+            2
+        );
+        assert_eq!(
+            hour_in("Europe/Berlin", datetime!(2023-10-29 01:30:00 UTC)),
+            2
+        );
+        assert_eq!(
+            hour_in("Europe/Berlin", datetime!(2023-10-29 02:00:00 UTC)),
+            3
+        );
+    }
+
+    #[test]
+    fn new_york_transitions() {
+        // 2023-03-12 07:00 UTC: 02:00 EST -> 03:00 EDT
+        assert_eq!(
+            hour_in("America/New_York", datetime!(2023-03-12 06:59:00 UTC)),
+            1
+        );
+        assert_eq!(
+            hour_in("America/New_York", datetime!(2023-03-12 07:00:00 UTC)),
+            3
+        );
+        // 2023-11-05 06:00 UTC: 02:00 EDT -> 01:00 EST
+        assert_eq!(
+            hour_in("America/New_York", datetime!(2023-11-05 05:59:00 UTC)),
+            1
+        );
+        assert_eq!(
+            hour_in("America/New_York", datetime!(2023-11-05 06:00:00 UTC)),
+            1
+        );
+    }
+
+    #[test]
+    fn fixed_offsets() {
+        let instant = datetime!(2023-06-01 12:00:00 UTC);
+        assert_eq!(hour_in("+1", instant), 13);
+        assert_eq!(hour_in("-03:30", instant), 8);
+        assert_eq!(hour_in("UTC+0545", instant), 17);
+        assert_eq!(hour_in("utc", instant), 12);
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        assert!(Zone::parse("Mars/Olympus_Mons").is_err());
+        assert!(Zone::parse("+15").is_err());
+        assert!(Zone::parse("").is_err());
+    }
+
+    #[test]
+    fn malformed_offsets_are_rejected() {
+        assert!(Zone::parse("+1é2").is_err());
+        assert!(Zone::parse("+-5").is_err());
+        assert!(Zone::parse("-+05:00").is_err());
+        assert!(Zone::parse("+05:-30").is_err());
+    }
+}
@@ -3,39 +3,65 @@
 // ====================================
 #![allow(unused)]
 
-mod vtx2f32;
-mod vtx2f64;
+//! Vectors, matrices and quaternions over any [`Float`]: `Vtx2T<T>` and so
+//! on, with `f32` and `f64` names for each. The plain names are `f64`:
+//!
+//! ```
+//! use vtx::{Mtx3, Qtx, Vtx3};
+//!
+//! let m = Mtx3::ident();
+//! let v = Vtx3::new();
+//! assert_eq!(v.magn() + Qtx::new().s, 0.0);
+//! assert_eq!(m, Mtx3::ident());
+//! ```
 
-mod vtx3f32;
-mod vtx3f64;
+mod float;
 
-mod mtx2f32;
-mod mtx2f64;
+mod vtx2;
+mod vtx3;
+mod vtx4;
 
-mod mtx3f32;
-mod mtx3f64;
+mod mtx2;
+mod mtx3;
+mod mtx4;
 
-mod qtxf32;
-mod qtxf64;
+mod qtx;
 
-pub use vtx2f32::Vtx2f32 as Vtx2f32;
-pub use vtx2f64::Vtx2f64 as Vtx2f64;
-pub use vtx2f64::Vtx2f64 as Vtx2;
+pub use float::Float;
 
-pub use vtx3f32::Vtx3f32 as Vtx3f32;
-pub use vtx3f64::Vtx3f64 as Vtx3f64;
-pub use vtx3f64::Vtx3f64 as Vtx3;
+pub use vtx2::Vtx2T;
+pub type Vtx2 = Vtx2T<f64>;
+pub type Vtx2f32 = Vtx2T<f32>;
+pub type Vtx2f64 = Vtx2T<f64>;
 
-pub use mtx2f32::Mtx2f32 as Mtx2f32;
-pub use mtx2f64::Mtx2f64 as Mtx2f64;
-pub use mtx2f64::Mtx2f64 as Mtx2;
+pub use vtx3::Vtx3T;
+pub type Vtx3 = Vtx3T<f64>;
+pub type Vtx3f32 = Vtx3T<f32>;
+pub type Vtx3f64 = Vtx3T<f64>;
 
-pub use mtx3f32::Mtx3f32 as Mtx3f32;
-pub use mtx3f64::Mtx3f64 as Mtx3f63;
-pub use mtx3f64::Mtx3f64 as Mtx3;
+pub use vtx4::Vtx4T;
+pub type Vtx4 = Vtx4T<f64>;
+pub type Vtx4f32 = Vtx4T<f32>;
+pub type Vtx4f64 = Vtx4T<f64>;
 
-pub use qtxf32::Qtxf32 as Qtxf32;
-pub use qtxf64::Qtxf64 as Qtxf64;
-pub use qtxf64::Qtxf64 as Qtx;
+pub use mtx2::Mtx2T;
+pub type Mtx2 = Mtx2T<f64>;
+pub type Mtx2f32 = Mtx2T<f32>;
+pub type Mtx2f64 = Mtx2T<f64>;
 
+pub use mtx3::Mtx3T;
+pub type Mtx3 = Mtx3T<f64>;
+pub type Mtx3f32 = Mtx3T<f32>;
+pub type Mtx3f64 = Mtx3T<f64>;
+#[deprecated(note = "misspelt, use `Mtx3f64`")]
+pub type Mtx3f63 = Mtx3T<f64>;
 
+pub use mtx4::Mtx4T;
+pub type Mtx4 = Mtx4T<f64>;
+pub type Mtx4f32 = Mtx4T<f32>;
+pub type Mtx4f64 = Mtx4T<f64>;
+
+pub use qtx::QtxT;
+pub type Qtx = QtxT<f64>;
+pub type Qtxf32 = QtxT<f32>;
+pub type Qtxf64 = QtxT<f64>;
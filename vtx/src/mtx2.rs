@@ -0,0 +1,241 @@
+// ====================================
+// ===== Matrix 2d
+// ====================================
+
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Float, Vtx2T};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Mtx2T<T: Float> {
+    pub ix: T,
+    pub iy: T,
+
+    pub jx: T,
+    pub jy: T,
+}
+
+impl<T: Float> Mtx2T<T> {
+    pub fn new() -> Mtx2T<T> {
+        Mtx2T {
+            ix: T::ZERO,
+            jx: T::ZERO,
+            iy: T::ZERO,
+            jy: T::ZERO,
+        }
+    }
+    pub fn build(ix: T, iy: T, jx: T, jy: T) -> Mtx2T<T> {
+        Mtx2T { ix, iy, jx, jy }
+    }
+    pub fn ident() -> Mtx2T<T> {
+        Mtx2T {
+            ix: T::ONE,
+            jx: T::ZERO,
+            iy: T::ZERO,
+            jy: T::ONE,
+        }
+    }
+    /// A quarter turn clockwise, unlike `from_rot`.
+    pub fn rot_90() -> Mtx2T<T> {
+        Mtx2T {
+            ix: T::ZERO,
+            jx: T::ONE,
+            iy: -T::ONE,
+            jy: T::ZERO,
+        }
+    }
+    pub fn rot_180() -> Mtx2T<T> {
+        Mtx2T {
+            ix: -T::ONE,
+            jx: T::ZERO,
+            iy: T::ZERO,
+            jy: -T::ONE,
+        }
+    }
+    pub fn rot_270() -> Mtx2T<T> {
+        Mtx2T {
+            ix: T::ZERO,
+            jx: -T::ONE,
+            iy: T::ONE,
+            jy: T::ZERO,
+        }
+    }
+    pub fn from_rot(rad: T) -> Mtx2T<T> {
+        Mtx2T {
+            ix: rad.cos(),
+            jx: -(rad.sin()),
+            iy: rad.sin(),
+            jy: rad.cos(),
+        }
+    }
+
+    /// Where the x axis goes: the first column.
+    pub fn i(&self) -> Vtx2T<T> {
+        Vtx2T {
+            x: self.ix,
+            y: self.iy,
+        }
+    }
+    pub fn j(&self) -> Vtx2T<T> {
+        Vtx2T {
+            x: self.jx,
+            y: self.jy,
+        }
+    }
+    /// The first row.
+    pub fn x(&self) -> Vtx2T<T> {
+        Vtx2T {
+            x: self.ix,
+            y: self.jx,
+        }
+    }
+    pub fn y(&self) -> Vtx2T<T> {
+        Vtx2T {
+            x: self.iy,
+            y: self.jy,
+        }
+    }
+    pub fn put_i(&self, v: Vtx2T<T>) -> Mtx2T<T> {
+        Mtx2T {
+            ix: v.x,
+            iy: v.y,
+            ..*self
+        }
+    }
+    pub fn put_j(&self, v: Vtx2T<T>) -> Mtx2T<T> {
+        Mtx2T {
+            jx: v.x,
+            jy: v.y,
+            ..*self
+        }
+    }
+    pub fn put_x(&self, v: Vtx2T<T>) -> Mtx2T<T> {
+        Mtx2T {
+            ix: v.x,
+            jx: v.y,
+            ..*self
+        }
+    }
+    pub fn put_y(&self, v: Vtx2T<T>) -> Mtx2T<T> {
+        Mtx2T {
+            iy: v.x,
+            jy: v.y,
+            ..*self
+        }
+    }
+    pub fn det(&self) -> T {
+        (self.ix * self.jy) - (self.iy * self.jx)
+    }
+    pub fn inverse(&self) -> Option<Mtx2T<T>> {
+        let det = self.det();
+        if det == T::ZERO {
+            None
+        } else {
+            Some(Mtx2T {
+                ix: self.jy / det,
+                jx: -self.jx / det,
+                iy: -self.iy / det,
+                jy: self.ix / det,
+            })
+        }
+    }
+    pub fn transpose(&self) -> Mtx2T<T> {
+        Mtx2T {
+            ix: self.ix,
+            iy: self.jx,
+            jx: self.iy,
+            jy: self.jy,
+        }
+    }
+    /// `self * rhs`: applies `rhs` first, then `self`.
+    pub fn combine(&self, rhs: Mtx2T<T>) -> Mtx2T<T> {
+        Mtx2T {
+            ix: (self.ix * rhs.ix) + (self.jx * rhs.iy),
+            jx: (self.ix * rhs.jx) + (self.jx * rhs.jy),
+            iy: (self.iy * rhs.ix) + (self.jy * rhs.iy),
+            jy: (self.iy * rhs.jx) + (self.jy * rhs.jy),
+        }
+    }
+    pub fn apply(self, rhs: Vtx2T<T>) -> Vtx2T<T> {
+        Vtx2T {
+            x: (self.ix * rhs.x) + (self.jx * rhs.y),
+            y: (self.iy * rhs.x) + (self.jy * rhs.y),
+        }
+    }
+}
+// ========== std::ops ==========
+// Addition
+impl<T: Float> Add<Mtx2T<T>> for Mtx2T<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Mtx2T {
+            ix: self.ix + rhs.ix,
+            iy: self.iy + rhs.iy,
+            jx: self.jx + rhs.jx,
+            jy: self.jy + rhs.jy,
+        }
+    }
+}
+impl<T: Float> AddAssign<Mtx2T<T>> for Mtx2T<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+// Subtraction
+impl<T: Float> Sub<Mtx2T<T>> for Mtx2T<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Mtx2T {
+            ix: self.ix - rhs.ix,
+            iy: self.iy - rhs.iy,
+            jx: self.jx - rhs.jx,
+            jy: self.jy - rhs.jy,
+        }
+    }
+}
+impl<T: Float> SubAssign<Mtx2T<T>> for Mtx2T<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+// Multiplication
+impl<T: Float> Mul<Mtx2T<T>> for Mtx2T<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.combine(rhs)
+    }
+}
+impl<T: Float> MulAssign<Mtx2T<T>> for Mtx2T<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.combine(rhs);
+    }
+}
+impl<T: Float> Mul<Vtx2T<T>> for Mtx2T<T> {
+    type Output = Vtx2T<T>;
+    fn mul(self, rhs: Vtx2T<T>) -> Vtx2T<T> {
+        self.apply(rhs)
+    }
+}
+impl<T: Float> Mul<T> for Mtx2T<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Mtx2T {
+            ix: self.ix * rhs,
+            iy: self.iy * rhs,
+            jx: self.jx * rhs,
+            jy: self.jy * rhs,
+        }
+    }
+}
+impl<T: Float> MulAssign<T> for Mtx2T<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+// Negation
+impl<T: Float> Neg for Mtx2T<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self * -T::ONE
+    }
+}
@@ -0,0 +1,340 @@
+// ====================================
+// ===== Matrix 3d
+// ====================================
+
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Float, Vtx3T};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Mtx3T<T: Float> {
+    pub ix: T,
+    pub iy: T,
+    pub iz: T,
+
+    pub jx: T,
+    pub jy: T,
+    pub jz: T,
+
+    pub kx: T,
+    pub ky: T,
+    pub kz: T,
+}
+
+impl<T: Float> Mtx3T<T> {
+    pub fn new() -> Mtx3T<T> {
+        Mtx3T {
+            ix: T::ZERO,
+            jx: T::ZERO,
+            kx: T::ZERO,
+            iy: T::ZERO,
+            jy: T::ZERO,
+            ky: T::ZERO,
+            iz: T::ZERO,
+            jz: T::ZERO,
+            kz: T::ZERO,
+        }
+    }
+    pub fn ident() -> Mtx3T<T> {
+        Mtx3T {
+            ix: T::ONE,
+            jx: T::ZERO,
+            kx: T::ZERO,
+            iy: T::ZERO,
+            jy: T::ONE,
+            ky: T::ZERO,
+            iz: T::ZERO,
+            jz: T::ZERO,
+            kz: T::ONE,
+        }
+    }
+
+    pub fn from_rot_x(rad: T) -> Mtx3T<T> {
+        Mtx3T {
+            ix: T::ONE,
+            jx: T::ZERO,
+            kx: T::ZERO,
+            iy: T::ZERO,
+            jy: rad.cos(),
+            ky: -(rad.sin()),
+            iz: T::ZERO,
+            jz: rad.sin(),
+            kz: rad.cos(),
+        }
+    }
+    pub fn from_rot_y(rad: T) -> Mtx3T<T> {
+        Mtx3T {
+            ix: rad.cos(),
+            jx: T::ZERO,
+            kx: rad.sin(),
+            iy: T::ZERO,
+            jy: T::ONE,
+            ky: T::ZERO,
+            iz: -(rad.sin()),
+            jz: T::ZERO,
+            kz: rad.cos(),
+        }
+    }
+    pub fn from_rot_z(rad: T) -> Mtx3T<T> {
+        Mtx3T {
+            ix: rad.cos(),
+            jx: -(rad.sin()),
+            kx: T::ZERO,
+            iy: rad.sin(),
+            jy: rad.cos(),
+            ky: T::ZERO,
+            iz: T::ZERO,
+            jz: T::ZERO,
+            kz: T::ONE,
+        }
+    }
+    /// Where the x axis goes: the first column.
+    pub fn i(&self) -> Vtx3T<T> {
+        Vtx3T {
+            x: self.ix,
+            y: self.iy,
+            z: self.iz,
+        }
+    }
+    pub fn j(&self) -> Vtx3T<T> {
+        Vtx3T {
+            x: self.jx,
+            y: self.jy,
+            z: self.jz,
+        }
+    }
+    pub fn k(&self) -> Vtx3T<T> {
+        Vtx3T {
+            x: self.kx,
+            y: self.ky,
+            z: self.kz,
+        }
+    }
+    /// The first row.
+    pub fn x(&self) -> Vtx3T<T> {
+        Vtx3T {
+            x: self.ix,
+            y: self.jx,
+            z: self.kx,
+        }
+    }
+    pub fn y(&self) -> Vtx3T<T> {
+        Vtx3T {
+            x: self.iy,
+            y: self.jy,
+            z: self.ky,
+        }
+    }
+    pub fn z(&self) -> Vtx3T<T> {
+        Vtx3T {
+            x: self.iz,
+            y: self.jz,
+            z: self.kz,
+        }
+    }
+    pub fn put_i(&self, v: Vtx3T<T>) -> Mtx3T<T> {
+        Mtx3T {
+            ix: v.x,
+            iy: v.y,
+            iz: v.z,
+            ..*self
+        }
+    }
+    pub fn put_j(&self, v: Vtx3T<T>) -> Mtx3T<T> {
+        Mtx3T {
+            jx: v.x,
+            jy: v.y,
+            jz: v.z,
+            ..*self
+        }
+    }
+    pub fn put_k(&self, v: Vtx3T<T>) -> Mtx3T<T> {
+        Mtx3T {
+            kx: v.x,
+            ky: v.y,
+            kz: v.z,
+            ..*self
+        }
+    }
+    pub fn put_x(&self, v: Vtx3T<T>) -> Mtx3T<T> {
+        Mtx3T {
+            ix: v.x,
+            jx: v.y,
+            kx: v.z,
+            ..*self
+        }
+    }
+    pub fn put_y(&self, v: Vtx3T<T>) -> Mtx3T<T> {
+        Mtx3T {
+            iy: v.x,
+            jy: v.y,
+            ky: v.z,
+            ..*self
+        }
+    }
+    pub fn put_z(&self, v: Vtx3T<T>) -> Mtx3T<T> {
+        Mtx3T {
+            iz: v.x,
+            jz: v.y,
+            kz: v.z,
+            ..*self
+        }
+    }
+    pub fn det(&self) -> T {
+        (self.ix * self.jy * self.kz)
+            + (self.jx * self.ky * self.iz)
+            + (self.kx * self.iy * self.jz)
+            - (self.iz * self.jy * self.kx)
+            - (self.jz * self.ky * self.ix)
+            - (self.kz * self.iy * self.jx)
+    }
+    pub fn inverse(&self) -> Option<Mtx3T<T>> {
+        let det = self.det();
+        if det == T::ZERO {
+            None
+        } else {
+            Some(Mtx3T {
+                ix: ((self.jy * self.kz) - (self.ky * self.jz)) / det,
+                jx: ((self.kx * self.jz) - (self.jx * self.kz)) / det,
+                kx: ((self.jx * self.ky) - (self.kx * self.jy)) / det,
+
+                iy: ((self.ky * self.iz) - (self.iy * self.kz)) / det,
+                jy: ((self.ix * self.kz) - (self.kx * self.iz)) / det,
+                ky: ((self.kx * self.iy) - (self.ix * self.ky)) / det,
+
+                iz: ((self.iy * self.jz) - (self.jy * self.iz)) / det,
+                jz: ((self.jx * self.iz) - (self.ix * self.jz)) / det,
+                kz: ((self.ix * self.jy) - (self.jx * self.iy)) / det,
+            })
+        }
+    }
+    pub fn transpose(&self) -> Mtx3T<T> {
+        Mtx3T {
+            ix: self.ix,
+            iy: self.jx,
+            iz: self.kx,
+
+            jx: self.iy,
+            jy: self.jy,
+            jz: self.ky,
+
+            kx: self.iz,
+            ky: self.jz,
+            kz: self.kz,
+        }
+    }
+    /// `self * m`: applies `m` first, then `self`.
+    pub fn combine(&self, m: Mtx3T<T>) -> Mtx3T<T> {
+        Mtx3T {
+            ix: (self.ix * m.ix) + (self.jx * m.iy) + (self.kx * m.iz),
+            iy: (self.iy * m.ix) + (self.jy * m.iy) + (self.ky * m.iz),
+            iz: (self.iz * m.ix) + (self.jz * m.iy) + (self.kz * m.iz),
+
+            jx: (self.ix * m.jx) + (self.jx * m.jy) + (self.kx * m.jz),
+            jy: (self.iy * m.jx) + (self.jy * m.jy) + (self.ky * m.jz),
+            jz: (self.iz * m.jx) + (self.jz * m.jy) + (self.kz * m.jz),
+
+            kx: (self.ix * m.kx) + (self.jx * m.ky) + (self.kx * m.kz),
+            ky: (self.iy * m.kx) + (self.jy * m.ky) + (self.ky * m.kz),
+            kz: (self.iz * m.kx) + (self.jz * m.ky) + (self.kz * m.kz),
+        }
+    }
+    pub fn apply(self, m: Vtx3T<T>) -> Vtx3T<T> {
+        Vtx3T {
+            x: (self.ix * m.x) + (self.jx * m.y) + (self.kx * m.z),
+            y: (self.iy * m.x) + (self.jy * m.y) + (self.ky * m.z),
+            z: (self.iz * m.x) + (self.jz * m.y) + (self.kz * m.z),
+        }
+    }
+}
+// ========== std::ops ==========
+// Addition
+impl<T: Float> Add<Mtx3T<T>> for Mtx3T<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Mtx3T {
+            ix: self.ix + rhs.ix,
+            iy: self.iy + rhs.iy,
+            iz: self.iz + rhs.iz,
+            jx: self.jx + rhs.jx,
+            jy: self.jy + rhs.jy,
+            jz: self.jz + rhs.jz,
+            kx: self.kx + rhs.kx,
+            ky: self.ky + rhs.ky,
+            kz: self.kz + rhs.kz,
+        }
+    }
+}
+impl<T: Float> AddAssign<Mtx3T<T>> for Mtx3T<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+// Subtraction
+impl<T: Float> Sub<Mtx3T<T>> for Mtx3T<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Mtx3T {
+            ix: self.ix - rhs.ix,
+            iy: self.iy - rhs.iy,
+            iz: self.iz - rhs.iz,
+            jx: self.jx - rhs.jx,
+            jy: self.jy - rhs.jy,
+            jz: self.jz - rhs.jz,
+            kx: self.kx - rhs.kx,
+            ky: self.ky - rhs.ky,
+            kz: self.kz - rhs.kz,
+        }
+    }
+}
+impl<T: Float> SubAssign<Mtx3T<T>> for Mtx3T<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+// Multiplication
+impl<T: Float> Mul<Mtx3T<T>> for Mtx3T<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.combine(rhs)
+    }
+}
+impl<T: Float> MulAssign<Mtx3T<T>> for Mtx3T<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.combine(rhs);
+    }
+}
+impl<T: Float> Mul<Vtx3T<T>> for Mtx3T<T> {
+    type Output = Vtx3T<T>;
+    fn mul(self, rhs: Vtx3T<T>) -> Vtx3T<T> {
+        self.apply(rhs)
+    }
+}
+impl<T: Float> Mul<T> for Mtx3T<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Mtx3T {
+            ix: self.ix * rhs,
+            iy: self.iy * rhs,
+            iz: self.iz * rhs,
+            jx: self.jx * rhs,
+            jy: self.jy * rhs,
+            jz: self.jz * rhs,
+            kx: self.kx * rhs,
+            ky: self.ky * rhs,
+            kz: self.kz * rhs,
+        }
+    }
+}
+impl<T: Float> MulAssign<T> for Mtx3T<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+// Negation
+impl<T: Float> Neg for Mtx3T<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self * -T::ONE
+    }
+}
@@ -0,0 +1,542 @@
+// ====================================
+// ===== Matrix 4d
+// ====================================
+
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Float, Mtx3T, QtxT, Vtx3T, Vtx4T};
+
+/// A homogeneous 3d transform. Columns `i`, `j` and `k` are where the axes
+/// go, `l` is the translation; rows `x`, `y`, `z` and `w`.
+///
+/// Cameras follow OpenGL: right-handed, looking down -z, and projections
+/// map the view onto -1..1 in every direction.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Mtx4T<T: Float> {
+    pub ix: T,
+    pub iy: T,
+    pub iz: T,
+    pub iw: T,
+
+    pub jx: T,
+    pub jy: T,
+    pub jz: T,
+    pub jw: T,
+
+    pub kx: T,
+    pub ky: T,
+    pub kz: T,
+    pub kw: T,
+
+    pub lx: T,
+    pub ly: T,
+    pub lz: T,
+    pub lw: T,
+}
+
+impl<T: Float> Mtx4T<T> {
+    pub fn new() -> Mtx4T<T> {
+        Mtx4T {
+            ix: T::ZERO,
+            iy: T::ZERO,
+            iz: T::ZERO,
+            iw: T::ZERO,
+            jx: T::ZERO,
+            jy: T::ZERO,
+            jz: T::ZERO,
+            jw: T::ZERO,
+            kx: T::ZERO,
+            ky: T::ZERO,
+            kz: T::ZERO,
+            kw: T::ZERO,
+            lx: T::ZERO,
+            ly: T::ZERO,
+            lz: T::ZERO,
+            lw: T::ZERO,
+        }
+    }
+    pub fn ident() -> Mtx4T<T> {
+        Mtx4T {
+            ix: T::ONE,
+            iy: T::ZERO,
+            iz: T::ZERO,
+            iw: T::ZERO,
+            jx: T::ZERO,
+            jy: T::ONE,
+            jz: T::ZERO,
+            jw: T::ZERO,
+            kx: T::ZERO,
+            ky: T::ZERO,
+            kz: T::ONE,
+            kw: T::ZERO,
+            lx: T::ZERO,
+            ly: T::ZERO,
+            lz: T::ZERO,
+            lw: T::ONE,
+        }
+    }
+    /// The linear map `m`, with no translation.
+    pub fn from_mtx3(m: Mtx3T<T>) -> Mtx4T<T> {
+        Mtx4T::ident()
+            .put_i(Vtx4T::from_dir(m.i()))
+            .put_j(Vtx4T::from_dir(m.j()))
+            .put_k(Vtx4T::from_dir(m.k()))
+    }
+    pub fn from_translation(v: Vtx3T<T>) -> Mtx4T<T> {
+        Mtx4T::ident().put_l(Vtx4T::from_point(v))
+    }
+    pub fn from_scale(v: Vtx3T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            ix: v.x,
+            jy: v.y,
+            kz: v.z,
+            ..Mtx4T::ident()
+        }
+    }
+    /// The view from `eye` towards `target`, with `up` pointing up on
+    /// screen. `up` must not be parallel to the view.
+    pub fn look_at(eye: Vtx3T<T>, target: Vtx3T<T>, up: Vtx3T<T>) -> Mtx4T<T> {
+        let f = (target - eye).unit();
+        let s = f.cross(up).unit();
+        let u = s.cross(f);
+        Mtx4T {
+            ix: s.x,
+            jx: s.y,
+            kx: s.z,
+            lx: -s.dot(eye),
+
+            iy: u.x,
+            jy: u.y,
+            ky: u.z,
+            ly: -u.dot(eye),
+
+            iz: -f.x,
+            jz: -f.y,
+            kz: -f.z,
+            lz: f.dot(eye),
+
+            iw: T::ZERO,
+            jw: T::ZERO,
+            kw: T::ZERO,
+            lw: T::ONE,
+        }
+    }
+    /// A perspective projection with the vertical field of view `fov_y`
+    /// in radians and `aspect` as width over height. `near` and `far` are
+    /// distances in front of the camera.
+    pub fn perspective(fov_y: T, aspect: T, near: T, far: T) -> Mtx4T<T> {
+        let f = T::ONE / (fov_y / T::TWO).tan();
+        Mtx4T {
+            ix: f / aspect,
+            jy: f,
+            kz: (far + near) / (near - far),
+            kw: -T::ONE,
+            lz: (T::TWO * far * near) / (near - far),
+            ..Mtx4T::new()
+        }
+    }
+    /// An orthographic projection of the box between the planes given.
+    pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mtx4T<T> {
+        Mtx4T {
+            ix: T::TWO / (right - left),
+            jy: T::TWO / (top - bottom),
+            kz: -T::TWO / (far - near),
+            lx: -(right + left) / (right - left),
+            ly: -(top + bottom) / (top - bottom),
+            lz: -(far + near) / (far - near),
+            ..Mtx4T::ident()
+        }
+    }
+    /// Where the x axis goes: the first column.
+    pub fn i(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.ix,
+            y: self.iy,
+            z: self.iz,
+            w: self.iw,
+        }
+    }
+    pub fn j(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.jx,
+            y: self.jy,
+            z: self.jz,
+            w: self.jw,
+        }
+    }
+    pub fn k(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.kx,
+            y: self.ky,
+            z: self.kz,
+            w: self.kw,
+        }
+    }
+    /// The translation: the last column.
+    pub fn l(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.lx,
+            y: self.ly,
+            z: self.lz,
+            w: self.lw,
+        }
+    }
+    /// The first row.
+    pub fn x(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.ix,
+            y: self.jx,
+            z: self.kx,
+            w: self.lx,
+        }
+    }
+    pub fn y(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.iy,
+            y: self.jy,
+            z: self.ky,
+            w: self.ly,
+        }
+    }
+    pub fn z(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.iz,
+            y: self.jz,
+            z: self.kz,
+            w: self.lz,
+        }
+    }
+    pub fn w(&self) -> Vtx4T<T> {
+        Vtx4T {
+            x: self.iw,
+            y: self.jw,
+            z: self.kw,
+            w: self.lw,
+        }
+    }
+    pub fn put_i(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            ix: v.x,
+            iy: v.y,
+            iz: v.z,
+            iw: v.w,
+            ..*self
+        }
+    }
+    pub fn put_j(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            jx: v.x,
+            jy: v.y,
+            jz: v.z,
+            jw: v.w,
+            ..*self
+        }
+    }
+    pub fn put_k(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            kx: v.x,
+            ky: v.y,
+            kz: v.z,
+            kw: v.w,
+            ..*self
+        }
+    }
+    pub fn put_l(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            lx: v.x,
+            ly: v.y,
+            lz: v.z,
+            lw: v.w,
+            ..*self
+        }
+    }
+    pub fn put_x(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            ix: v.x,
+            jx: v.y,
+            kx: v.z,
+            lx: v.w,
+            ..*self
+        }
+    }
+    pub fn put_y(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            iy: v.x,
+            jy: v.y,
+            ky: v.z,
+            ly: v.w,
+            ..*self
+        }
+    }
+    pub fn put_z(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            iz: v.x,
+            jz: v.y,
+            kz: v.z,
+            lz: v.w,
+            ..*self
+        }
+    }
+    pub fn put_w(&self, v: Vtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            iw: v.x,
+            jw: v.y,
+            kw: v.z,
+            lw: v.w,
+            ..*self
+        }
+    }
+    /// The entries as `[row][column]`.
+    fn rows(&self) -> [[T; 4]; 4] {
+        [
+            [self.ix, self.jx, self.kx, self.lx],
+            [self.iy, self.jy, self.ky, self.ly],
+            [self.iz, self.jz, self.kz, self.lz],
+            [self.iw, self.jw, self.kw, self.lw],
+        ]
+    }
+    /// The determinant of what is left without `row` and `col`.
+    fn minor(&self, row: usize, col: usize) -> T {
+        let rows = self.rows();
+        let mut m = [T::ZERO; 9];
+        let kept = (0..4)
+            .filter(|&r| r != row)
+            .flat_map(|r| (0..4).filter(move |&c| c != col).map(move |c| rows[r][c]));
+        for (entry, value) in m.iter_mut().zip(kept) {
+            *entry = value;
+        }
+        Mtx3T {
+            ix: m[0],
+            jx: m[1],
+            kx: m[2],
+            iy: m[3],
+            jy: m[4],
+            ky: m[5],
+            iz: m[6],
+            jz: m[7],
+            kz: m[8],
+        }
+        .det()
+    }
+    fn cofactor(&self, row: usize, col: usize) -> T {
+        match (row + col) % 2 {
+            0 => self.minor(row, col),
+            _ => -self.minor(row, col),
+        }
+    }
+    pub fn det(&self) -> T {
+        let rows = self.rows();
+        (0..4).fold(T::ZERO, |det, col| {
+            det + rows[0][col] * self.cofactor(0, col)
+        })
+    }
+    /// The transposed cofactors over the determinant.
+    pub fn inverse(&self) -> Option<Mtx4T<T>> {
+        let det = self.det();
+        if det == T::ZERO {
+            return None;
+        }
+        let col = |c| {
+            Vtx4T::build(
+                self.cofactor(c, 0) / det,
+                self.cofactor(c, 1) / det,
+                self.cofactor(c, 2) / det,
+                self.cofactor(c, 3) / det,
+            )
+        };
+        Some(
+            Mtx4T::new()
+                .put_i(col(0))
+                .put_j(col(1))
+                .put_k(col(2))
+                .put_l(col(3)),
+        )
+    }
+    pub fn transpose(&self) -> Mtx4T<T> {
+        Mtx4T {
+            ix: self.ix,
+            iy: self.jx,
+            iz: self.kx,
+            iw: self.lx,
+
+            jx: self.iy,
+            jy: self.jy,
+            jz: self.ky,
+            jw: self.ly,
+
+            kx: self.iz,
+            ky: self.jz,
+            kz: self.kz,
+            kw: self.lz,
+
+            lx: self.iw,
+            ly: self.jw,
+            lz: self.kw,
+            lw: self.lw,
+        }
+    }
+    /// `self * m`: applies `m` first, then `self`.
+    pub fn combine(&self, m: Mtx4T<T>) -> Mtx4T<T> {
+        Mtx4T {
+            ix: (self.ix * m.ix) + (self.jx * m.iy) + (self.kx * m.iz) + (self.lx * m.iw),
+            iy: (self.iy * m.ix) + (self.jy * m.iy) + (self.ky * m.iz) + (self.ly * m.iw),
+            iz: (self.iz * m.ix) + (self.jz * m.iy) + (self.kz * m.iz) + (self.lz * m.iw),
+            iw: (self.iw * m.ix) + (self.jw * m.iy) + (self.kw * m.iz) + (self.lw * m.iw),
+
+            jx: (self.ix * m.jx) + (self.jx * m.jy) + (self.kx * m.jz) + (self.lx * m.jw),
+            jy: (self.iy * m.jx) + (self.jy * m.jy) + (self.ky * m.jz) + (self.ly * m.jw),
+            jz: (self.iz * m.jx) + (self.jz * m.jy) + (self.kz * m.jz) + (self.lz * m.jw),
+            jw: (self.iw * m.jx) + (self.jw * m.jy) + (self.kw * m.jz) + (self.lw * m.jw),
+
+            kx: (self.ix * m.kx) + (self.jx * m.ky) + (self.kx * m.kz) + (self.lx * m.kw),
+            ky: (self.iy * m.kx) + (self.jy * m.ky) + (self.ky * m.kz) + (self.ly * m.kw),
+            kz: (self.iz * m.kx) + (self.jz * m.ky) + (self.kz * m.kz) + (self.lz * m.kw),
+            kw: (self.iw * m.kx) + (self.jw * m.ky) + (self.kw * m.kz) + (self.lw * m.kw),
+
+            lx: (self.ix * m.lx) + (self.jx * m.ly) + (self.kx * m.lz) + (self.lx * m.lw),
+            ly: (self.iy * m.lx) + (self.jy * m.ly) + (self.ky * m.lz) + (self.ly * m.lw),
+            lz: (self.iz * m.lx) + (self.jz * m.ly) + (self.kz * m.lz) + (self.lz * m.lw),
+            lw: (self.iw * m.lx) + (self.jw * m.ly) + (self.kw * m.lz) + (self.lw * m.lw),
+        }
+    }
+    pub fn apply(self, v: Vtx4T<T>) -> Vtx4T<T> {
+        Vtx4T {
+            x: (self.ix * v.x) + (self.jx * v.y) + (self.kx * v.z) + (self.lx * v.w),
+            y: (self.iy * v.x) + (self.jy * v.y) + (self.ky * v.z) + (self.ly * v.w),
+            z: (self.iz * v.x) + (self.jz * v.y) + (self.kz * v.z) + (self.lz * v.w),
+            w: (self.iw * v.x) + (self.jw * v.y) + (self.kw * v.z) + (self.lw * v.w),
+        }
+    }
+    /// Moves the point `v`, dividing by `w` after a projection.
+    pub fn apply_point(self, v: Vtx3T<T>) -> Vtx3T<T> {
+        self.apply(Vtx4T::from_point(v)).as_point()
+    }
+    /// Turns the direction `v`, ignoring the translation.
+    pub fn apply_dir(self, v: Vtx3T<T>) -> Vtx3T<T> {
+        self.apply(Vtx4T::from_dir(v)).xyz()
+    }
+}
+// ========== std::ops ==========
+// Addition
+impl<T: Float> Add<Mtx4T<T>> for Mtx4T<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Mtx4T {
+            ix: self.ix + rhs.ix,
+            iy: self.iy + rhs.iy,
+            iz: self.iz + rhs.iz,
+            iw: self.iw + rhs.iw,
+            jx: self.jx + rhs.jx,
+            jy: self.jy + rhs.jy,
+            jz: self.jz + rhs.jz,
+            jw: self.jw + rhs.jw,
+            kx: self.kx + rhs.kx,
+            ky: self.ky + rhs.ky,
+            kz: self.kz + rhs.kz,
+            kw: self.kw + rhs.kw,
+            lx: self.lx + rhs.lx,
+            ly: self.ly + rhs.ly,
+            lz: self.lz + rhs.lz,
+            lw: self.lw + rhs.lw,
+        }
+    }
+}
+impl<T: Float> AddAssign<Mtx4T<T>> for Mtx4T<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+// Subtraction
+impl<T: Float> Sub<Mtx4T<T>> for Mtx4T<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Mtx4T {
+            ix: self.ix - rhs.ix,
+            iy: self.iy - rhs.iy,
+            iz: self.iz - rhs.iz,
+            iw: self.iw - rhs.iw,
+            jx: self.jx - rhs.jx,
+            jy: self.jy - rhs.jy,
+            jz: self.jz - rhs.jz,
+            jw: self.jw - rhs.jw,
+            kx: self.kx - rhs.kx,
+            ky: self.ky - rhs.ky,
+            kz: self.kz - rhs.kz,
+            kw: self.kw - rhs.kw,
+            lx: self.lx - rhs.lx,
+            ly: self.ly - rhs.ly,
+            lz: self.lz - rhs.lz,
+            lw: self.lw - rhs.lw,
+        }
+    }
+}
+impl<T: Float> SubAssign<Mtx4T<T>> for Mtx4T<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+// Multiplication
+impl<T: Float> Mul<Mtx4T<T>> for Mtx4T<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.combine(rhs)
+    }
+}
+impl<T: Float> MulAssign<Mtx4T<T>> for Mtx4T<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.combine(rhs);
+    }
+}
+impl<T: Float> Mul<Vtx4T<T>> for Mtx4T<T> {
+    type Output = Vtx4T<T>;
+    fn mul(self, rhs: Vtx4T<T>) -> Vtx4T<T> {
+        self.apply(rhs)
+    }
+}
+impl<T: Float> Mul<T> for Mtx4T<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Mtx4T {
+            ix: self.ix * rhs,
+            iy: self.iy * rhs,
+            iz: self.iz * rhs,
+            iw: self.iw * rhs,
+            jx: self.jx * rhs,
+            jy: self.jy * rhs,
+            jz: self.jz * rhs,
+            jw: self.jw * rhs,
+            kx: self.kx * rhs,
+            ky: self.ky * rhs,
+            kz: self.kz * rhs,
+            kw: self.kw * rhs,
+            lx: self.lx * rhs,
+            ly: self.ly * rhs,
+            lz: self.lz * rhs,
+            lw: self.lw * rhs,
+        }
+    }
+}
+impl<T: Float> MulAssign<T> for Mtx4T<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+// Negation
+impl<T: Float> Neg for Mtx4T<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self * -T::ONE
+    }
+}
+// ========== From ==========
+impl<T: Float> From<Mtx3T<T>> for Mtx4T<T> {
+    fn from(m: Mtx3T<T>) -> Self {
+        Mtx4T::from_mtx3(m)
+    }
+}
+/// The rotation of a unit quaternion.
+impl<T: Float> From<QtxT<T>> for Mtx4T<T> {
+    fn from(q: QtxT<T>) -> Self {
+        Mtx4T::from_mtx3(q.into())
+    }
+}
@@ -0,0 +1,248 @@
+// ====================================
+// ===== Quaternion
+// ====================================
+
+use std::ops::{Add, Mul, MulAssign, Neg, Sub};
+
+use crate::{Float, Mtx3T, Vtx3T};
+
+/// `s + u.x i + u.y j + u.z k`. Rotations are the unit ones.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct QtxT<T: Float> {
+    pub s: T,
+    pub u: Vtx3T<T>,
+}
+impl<T: Float> QtxT<T> {
+    pub fn new() -> QtxT<T> {
+        QtxT {
+            s: T::ZERO,
+            u: Vtx3T::new(),
+        }
+    }
+    pub fn build(s: T, u: Vtx3T<T>) -> QtxT<T> {
+        QtxT { s, u }
+    }
+    /// No rotation.
+    pub fn ident() -> QtxT<T> {
+        QtxT {
+            s: T::ONE,
+            u: Vtx3T::new(),
+        }
+    }
+    /// Turns by `angle` around the unit vector `axis`, counterclockwise
+    /// looking down the axis.
+    pub fn from_axis_angle(axis: Vtx3T<T>, angle: T) -> QtxT<T> {
+        let half = angle / T::TWO;
+        QtxT {
+            s: half.cos(),
+            u: Vtx3T {
+                x: half.sin() * axis.x,
+                y: half.sin() * axis.y,
+                z: half.sin() * axis.z,
+            },
+        }
+    }
+    /// The unit axis and the angle in `0..=2pi` of a unit quaternion; the
+    /// x axis when there is no rotation.
+    pub fn to_axis_angle(&self) -> (Vtx3T<T>, T) {
+        let sin = self.u.magn();
+        let angle = T::TWO * T::atan2(sin, self.s);
+        if sin == T::ZERO {
+            (Vtx3T::build(T::ONE, T::ZERO, T::ZERO), angle)
+        } else {
+            (self.u / sin, angle)
+        }
+    }
+    /// The same angle around another axis.
+    pub fn put_axis(&self, axis: Vtx3T<T>) -> QtxT<T> {
+        QtxT::from_axis_angle(axis, self.to_axis_angle().1)
+    }
+    /// The same axis with another angle.
+    pub fn put_angle(&self, angle: T) -> QtxT<T> {
+        QtxT::from_axis_angle(self.to_axis_angle().0, angle)
+    }
+
+    pub fn dot(&self, q: QtxT<T>) -> T {
+        (self.s * q.s) + self.u.dot(q.u)
+    }
+    pub fn magn(&self) -> T {
+        self.dot(*self).sqrt()
+    }
+    pub fn unit(&self) -> QtxT<T> {
+        QtxT {
+            s: self.s / self.magn(),
+            u: self.u / self.magn(),
+        }
+    }
+    pub fn conj(&self) -> QtxT<T> {
+        QtxT {
+            s: self.s,
+            u: self.u * -T::ONE,
+        }
+    }
+    /// The conjugate for unit quaternions; `None` for zero.
+    pub fn inverse(&self) -> Option<QtxT<T>> {
+        let magn2 = self.dot(*self);
+        if magn2 == T::ZERO {
+            None
+        } else {
+            Some(QtxT {
+                s: self.s / magn2,
+                u: self.u * (-T::ONE / magn2),
+            })
+        }
+    }
+    /// The Hamilton product `self * q`: rotates by `q` first, then `self`.
+    pub fn combine(&self, q: QtxT<T>) -> QtxT<T> {
+        QtxT {
+            s: (self.s * q.s) - self.u.dot(q.u),
+            u: (q.u * self.s) + (self.u * q.s) + self.u.cross(q.u),
+        }
+    }
+
+    // v = vector to be rotated; u = vector part of quaternion; s = scalar part of quatenion
+    // rotated_vec = 2 * dot(uv) * u + (s * s - dot(uu) ) * v + 2 * s * cross(uv)
+
+    pub fn apply(&self, v: Vtx3T<T>) -> Vtx3T<T> {
+        (self.u * (T::TWO * self.u.dot(v)))
+            + (v * ((self.s * self.s) - self.u.dot(self.u)))
+            + (self.u.cross(v) * (T::TWO * self.s))
+    }
+
+    /// Normalised linear interpolation from `self` at `t = 0` to `q` at
+    /// `t = 1`, the short way round. Cheaper than `slerp`, but the speed
+    /// is not constant.
+    pub fn nlerp(&self, q: QtxT<T>, t: T) -> QtxT<T> {
+        let q = if self.dot(q) < T::ZERO { -q } else { q };
+        ((*self * (T::ONE - t)) + (q * t)).unit()
+    }
+    /// Spherical interpolation between unit quaternions: turns from `self`
+    /// to `q` at a constant speed, the short way round.
+    pub fn slerp(&self, q: QtxT<T>, t: T) -> QtxT<T> {
+        let (q, cos) = match self.dot(q) {
+            cos if cos < T::ZERO => (-q, -cos),
+            cos => (q, cos),
+        };
+        // the same rotation: the sine below would be zero. Close ones are
+        // fine, as the ratios of the sines tend to `1 - t` and `t`.
+        if cos >= T::ONE {
+            return self.nlerp(q, t);
+        }
+        let angle = cos.acos();
+        let sin = angle.sin();
+        (*self * (((T::ONE - t) * angle).sin() / sin)) + (q * ((t * angle).sin() / sin))
+    }
+}
+// ========== std::ops ==========
+// Addition
+impl<T: Float> Add<QtxT<T>> for QtxT<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        QtxT {
+            s: self.s + rhs.s,
+            u: self.u + rhs.u,
+        }
+    }
+}
+// Subtraction
+impl<T: Float> Sub<QtxT<T>> for QtxT<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        QtxT {
+            s: self.s - rhs.s,
+            u: self.u - rhs.u,
+        }
+    }
+}
+// Multiplication
+impl<T: Float> Mul<QtxT<T>> for QtxT<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.combine(rhs)
+    }
+}
+impl<T: Float> MulAssign<QtxT<T>> for QtxT<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.combine(rhs);
+    }
+}
+impl<T: Float> Mul<Vtx3T<T>> for QtxT<T> {
+    type Output = Vtx3T<T>;
+    fn mul(self, rhs: Vtx3T<T>) -> Vtx3T<T> {
+        self.apply(rhs)
+    }
+}
+impl<T: Float> Mul<T> for QtxT<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        QtxT {
+            s: self.s * rhs,
+            u: self.u * rhs,
+        }
+    }
+}
+// Negation
+impl<T: Float> Neg for QtxT<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self * -T::ONE
+    }
+}
+// ========== From ==========
+/// The rotation matrix of a unit quaternion.
+impl<T: Float> From<QtxT<T>> for Mtx3T<T> {
+    fn from(q: QtxT<T>) -> Self {
+        let QtxT {
+            s,
+            u: Vtx3T { x, y, z },
+        } = q;
+        let two = T::TWO;
+        Mtx3T {
+            ix: T::ONE - two * (y * y + z * z),
+            iy: two * (x * y + s * z),
+            iz: two * (x * z - s * y),
+
+            jx: two * (x * y - s * z),
+            jy: T::ONE - two * (x * x + z * z),
+            jz: two * (y * z + s * x),
+
+            kx: two * (x * z + s * y),
+            ky: two * (y * z - s * x),
+            kz: T::ONE - two * (x * x + y * y),
+        }
+    }
+}
+/// The unit quaternion of a rotation matrix.
+impl<T: Float> From<Mtx3T<T>> for QtxT<T> {
+    fn from(m: Mtx3T<T>) -> Self {
+        let (one, four) = (T::ONE, T::from_f64(4.0));
+        let trace = m.ix + m.jy + m.kz;
+        // divide by the largest of the four, so nothing small is divided by
+        let q = if trace > T::ZERO {
+            let d = (trace + one).sqrt() * T::TWO;
+            QtxT::build(
+                d / four,
+                Vtx3T::build((m.jz - m.ky) / d, (m.kx - m.iz) / d, (m.iy - m.jx) / d),
+            )
+        } else if m.ix > m.jy && m.ix > m.kz {
+            let d = (one + m.ix - m.jy - m.kz).sqrt() * T::TWO;
+            QtxT::build(
+                (m.jz - m.ky) / d,
+                Vtx3T::build(d / four, (m.jx + m.iy) / d, (m.kx + m.iz) / d),
+            )
+        } else if m.jy > m.kz {
+            let d = (one + m.jy - m.ix - m.kz).sqrt() * T::TWO;
+            QtxT::build(
+                (m.kx - m.iz) / d,
+                Vtx3T::build((m.jx + m.iy) / d, d / four, (m.ky + m.jz) / d),
+            )
+        } else {
+            let d = (one + m.kz - m.ix - m.jy).sqrt() * T::TWO;
+            QtxT::build(
+                (m.iy - m.jx) / d,
+                Vtx3T::build((m.kx + m.iz) / d, (m.ky + m.jz) / d, d / four),
+            )
+        };
+        q.unit()
+    }
+}
@@ -1,54 +1,59 @@
 // ====================================
-// ===== Vector 2d f32
+// ===== Vector 2d
 // ====================================
 
 use std::ops::{Add, Div, Mul, Sub};
 
+use crate::Float;
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
-pub struct Vtx2f32 {
-    pub x: f32,
-    pub y: f32,
+pub struct Vtx2T<T: Float> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Vtx2f32 {
-    pub fn new() -> Vtx2f32 {
-        Vtx2f32 { x: 0.0, y: 0.0 }
+impl<T: Float> Vtx2T<T> {
+    pub fn new() -> Vtx2T<T> {
+        Vtx2T {
+            x: T::ZERO,
+            y: T::ZERO,
+        }
     }
-    pub fn build(x: f32, y: f32) -> Vtx2f32 {
-        Vtx2f32 { x, y }
+    pub fn build(x: T, y: T) -> Vtx2T<T> {
+        Vtx2T { x, y }
     }
-    pub fn from_rot(rot: f32) -> Vtx2f32 {
-        Vtx2f32 {
+    pub fn from_rot(rot: T) -> Vtx2T<T> {
+        Vtx2T {
             x: rot.cos(),
             y: rot.sin(),
         }
     }
 
-    pub fn dot(&self, v: Vtx2f32) -> f32 {
+    pub fn dot(&self, v: Vtx2T<T>) -> T {
         (self.x * v.x) + (self.y * v.y)
     }
-    pub fn cross(&self, v: Vtx2f32) -> f32 {
+    pub fn cross(&self, v: Vtx2T<T>) -> T {
         (self.x * v.y) - (self.y * v.x)
     }
-    pub fn dist(&self, v: Vtx2f32) -> f32 {
+    pub fn dist(&self, v: Vtx2T<T>) -> T {
         ((self.x - v.x).powi(2) + (self.y - v.y).powi(2)).sqrt()
     }
-    pub fn magn(&self) -> f32 {
+    pub fn magn(&self) -> T {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
-    pub fn unit(&self) -> Vtx2f32 {
-        Vtx2f32 {
+    pub fn unit(&self) -> Vtx2T<T> {
+        Vtx2T {
             x: self.x / self.magn(),
             y: self.y / self.magn(),
         }
     }
-    pub fn as_rot(&self) -> f32 {
-        f32::atan2(self.unit().y, self.unit().x)
+    pub fn as_rot(&self) -> T {
+        T::atan2(self.unit().y, self.unit().x)
     }
 }
 // ========== std::ops ==========
 // Addition
-impl Add<Vtx2f32> for Vtx2f32 {
+impl<T: Float> Add<Vtx2T<T>> for Vtx2T<T> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
         Self {
@@ -57,9 +62,9 @@ impl Add<Vtx2f32> for Vtx2f32 {
         }
     }
 }
-impl Add<f32> for Vtx2f32 {
+impl<T: Float> Add<T> for Vtx2T<T> {
     type Output = Self;
-    fn add(self, rhs: f32) -> Self {
+    fn add(self, rhs: T) -> Self {
         Self {
             x: self.x + rhs,
             y: self.y + rhs,
@@ -67,7 +72,7 @@ impl Add<f32> for Vtx2f32 {
     }
 }
 // Subtraction
-impl Sub<Vtx2f32> for Vtx2f32 {
+impl<T: Float> Sub<Vtx2T<T>> for Vtx2T<T> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
         Self {
@@ -76,9 +81,9 @@ impl Sub<Vtx2f32> for Vtx2f32 {
         }
     }
 }
-impl Sub<f32> for Vtx2f32 {
+impl<T: Float> Sub<T> for Vtx2T<T> {
     type Output = Self;
-    fn sub(self, rhs: f32) -> Self {
+    fn sub(self, rhs: T) -> Self {
         Self {
             x: self.x - rhs,
             y: self.y - rhs,
@@ -86,18 +91,18 @@ impl Sub<f32> for Vtx2f32 {
     }
 }
 // Multiplication
-impl Mul<Vtx2f32> for Vtx2f32 {
+impl<T: Float> Mul<Vtx2T<T>> for Vtx2T<T> {
     type Output = Self;
-    fn mul(self, rhs: Vtx2f32) -> Self {
+    fn mul(self, rhs: Vtx2T<T>) -> Self {
         Self {
             x: self.x * rhs.x,
             y: self.y * rhs.y,
         }
     }
 }
-impl Mul<f32> for Vtx2f32 {
+impl<T: Float> Mul<T> for Vtx2T<T> {
     type Output = Self;
-    fn mul(self, rhs: f32) -> Self {
+    fn mul(self, rhs: T) -> Self {
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -105,18 +110,18 @@ impl Mul<f32> for Vtx2f32 {
     }
 }
 // Division
-impl Div<Vtx2f32> for Vtx2f32 {
+impl<T: Float> Div<Vtx2T<T>> for Vtx2T<T> {
     type Output = Self;
-    fn div(self, rhs: Vtx2f32) -> Self {
+    fn div(self, rhs: Vtx2T<T>) -> Self {
         Self {
             x: self.x / rhs.x,
             y: self.y / rhs.y,
         }
     }
 }
-impl Div<f32> for Vtx2f32 {
+impl<T: Float> Div<T> for Vtx2T<T> {
     type Output = Self;
-    fn div(self, rhs: f32) -> Self {
+    fn div(self, rhs: T) -> Self {
         Self {
             x: self.x / rhs,
             y: self.y / rhs,
@@ -124,16 +129,16 @@ impl Div<f32> for Vtx2f32 {
     }
 }
 // ========== From ==========
-impl From<(f32, f32)> for Vtx2f32 {
-    fn from(value: (f32, f32)) -> Self {
-        Vtx2f32 {
+impl<T: Float> From<(T, T)> for Vtx2T<T> {
+    fn from(value: (T, T)) -> Self {
+        Vtx2T {
             x: value.0,
             y: value.1,
         }
     }
 }
-impl From<Vtx2f32> for (f32, f32) {
-    fn from(value: Vtx2f32) -> Self {
+impl<T: Float> From<Vtx2T<T>> for (T, T) {
+    fn from(value: Vtx2T<T>) -> Self {
         (value.x, value.y)
     }
 }
@@ -1,77 +1,87 @@
 // ====================================
-// ===== Vector 3d f32
+// ===== Vector 3d
 // ====================================
 
 use std::ops::{Add, Div, Mul, Sub};
-use crate::Vtx2f32;
+
+use crate::{Float, Vtx2T};
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
-pub struct Vtx3f32 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+pub struct Vtx3T<T: Float> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl Vtx3f32 {
-    pub fn new() -> Vtx3f32 {
-        Vtx3f32 {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
+impl<T: Float> Vtx3T<T> {
+    pub fn new() -> Vtx3T<T> {
+        Vtx3T {
+            x: T::ZERO,
+            y: T::ZERO,
+            z: T::ZERO,
         }
     }
-    pub fn build(x: f32, y: f32, z: f32) -> Vtx3f32 {
-        Vtx3f32 { x, y, z }
+    pub fn build(x: T, y: T, z: T) -> Vtx3T<T> {
+        Vtx3T { x, y, z }
     }
-    pub fn dot(&self, v: Vtx3f32) -> f32 {
+    pub fn dot(&self, v: Vtx3T<T>) -> T {
         (self.x * v.x) + (self.y * v.y) + (self.z * v.z)
     }
-    pub fn cross(&self, v: Vtx3f32) -> Vtx3f32 {
-        Vtx3f32 {
+    pub fn cross(&self, v: Vtx3T<T>) -> Vtx3T<T> {
+        Vtx3T {
             x: (self.y * v.z) - (self.z * v.y),
             y: (self.z * v.x) - (self.x * v.z),
             z: (self.x * v.y) - (self.y * v.x),
         }
     }
-    pub fn dist(&self, v: Vtx3f32) -> f32 {
+    pub fn dist(&self, v: Vtx3T<T>) -> T {
         ((self.x - v.x).powi(2) + (self.y - v.y).powi(2) + (self.z - v.z).powi(2)).sqrt()
     }
-    pub fn magn(&self) -> f32 {
+    pub fn magn(&self) -> T {
         (self.x.powi(2) + self.y.powi(2) + self.z.powi(2)).sqrt()
     }
-    pub fn unit(&self) -> Vtx3f32 {
-        Vtx3f32 {
+    pub fn unit(&self) -> Vtx3T<T> {
+        Vtx3T {
             x: self.x / self.magn(),
             y: self.y / self.magn(),
             z: self.z / self.magn(),
         }
     }
-    pub fn xy(&self) -> Vtx2f32 {
-        Vtx2f32 { x: self.x, y: self.y }
+    pub fn xy(&self) -> Vtx2T<T> {
+        Vtx2T {
+            x: self.x,
+            y: self.y,
+        }
     }
-    pub fn xz(&self) -> Vtx2f32 {
-        Vtx2f32 { x: self.x, y: self.z }
+    pub fn xz(&self) -> Vtx2T<T> {
+        Vtx2T {
+            x: self.x,
+            y: self.z,
+        }
     }
-    pub fn yz(&self) -> Vtx2f32 {
-        Vtx2f32 { x: self.y, y: self.z }
+    pub fn yz(&self) -> Vtx2T<T> {
+        Vtx2T {
+            x: self.y,
+            y: self.z,
+        }
     }
 }
 // ========== std::ops ==========
 // Addition
-impl Add<Vtx3f32> for Vtx3f32 {
+impl<T: Float> Add<Vtx3T<T>> for Vtx3T<T> {
     type Output = Self;
-    fn add(self, rhs: Vtx3f32) -> Self {
-        Vtx3f32 {
+    fn add(self, rhs: Vtx3T<T>) -> Self {
+        Vtx3T {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
             z: self.z + rhs.z,
         }
     }
 }
-impl Add<f32> for Vtx3f32 {
+impl<T: Float> Add<T> for Vtx3T<T> {
     type Output = Self;
-    fn add(self, rhs: f32) -> Self {
-        Vtx3f32 {
+    fn add(self, rhs: T) -> Self {
+        Vtx3T {
             x: self.x + rhs,
             y: self.y + rhs,
             z: self.z + rhs,
@@ -79,20 +89,20 @@ impl Add<f32> for Vtx3f32 {
     }
 }
 //Subtraction
-impl Sub<Vtx3f32> for Vtx3f32 {
+impl<T: Float> Sub<Vtx3T<T>> for Vtx3T<T> {
     type Output = Self;
-    fn sub(self, rhs: Vtx3f32) -> Self {
-        Vtx3f32 {
+    fn sub(self, rhs: Vtx3T<T>) -> Self {
+        Vtx3T {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
             z: self.z - rhs.z,
         }
     }
 }
-impl Sub<f32> for Vtx3f32 {
+impl<T: Float> Sub<T> for Vtx3T<T> {
     type Output = Self;
-    fn sub(self, rhs: f32) -> Self {
-        Vtx3f32 {
+    fn sub(self, rhs: T) -> Self {
+        Vtx3T {
             x: self.x - rhs,
             y: self.y - rhs,
             z: self.z - rhs,
@@ -100,20 +110,20 @@ impl Sub<f32> for Vtx3f32 {
     }
 }
 // Multiplication
-impl Mul<Vtx3f32> for Vtx3f32 {
+impl<T: Float> Mul<Vtx3T<T>> for Vtx3T<T> {
     type Output = Self;
-    fn mul(self, rhs: Vtx3f32) -> Self {
-        Vtx3f32 {
+    fn mul(self, rhs: Vtx3T<T>) -> Self {
+        Vtx3T {
             x: self.x * rhs.x,
             y: self.y * rhs.y,
             z: self.z * rhs.z,
         }
     }
 }
-impl Mul<f32> for Vtx3f32 {
+impl<T: Float> Mul<T> for Vtx3T<T> {
     type Output = Self;
-    fn mul(self, rhs: f32) -> Self {
-        Vtx3f32 {
+    fn mul(self, rhs: T) -> Self {
+        Vtx3T {
             x: self.x * rhs,
             y: self.y * rhs,
             z: self.z * rhs,
@@ -121,20 +131,20 @@ impl Mul<f32> for Vtx3f32 {
     }
 }
 // Division
-impl Div<Vtx3f32> for Vtx3f32 {
+impl<T: Float> Div<Vtx3T<T>> for Vtx3T<T> {
     type Output = Self;
-    fn div(self, rhs: Vtx3f32) -> Self {
-        Vtx3f32 {
+    fn div(self, rhs: Vtx3T<T>) -> Self {
+        Vtx3T {
             x: self.x / rhs.x,
             y: self.y / rhs.y,
             z: self.z / rhs.z,
         }
     }
 }
-impl Div<f32> for Vtx3f32 {
+impl<T: Float> Div<T> for Vtx3T<T> {
     type Output = Self;
-    fn div(self, rhs: f32) -> Self {
-        Vtx3f32 {
+    fn div(self, rhs: T) -> Self {
+        Vtx3T {
             x: self.x / rhs,
             y: self.y / rhs,
             z: self.z / rhs,
@@ -142,17 +152,17 @@ impl Div<f32> for Vtx3f32 {
     }
 }
 // ========== From ===========
-impl From<(f32, f32, f32)> for Vtx3f32 {
-    fn from(value: (f32, f32, f32)) -> Self {
-        Vtx3f32 {
+impl<T: Float> From<(T, T, T)> for Vtx3T<T> {
+    fn from(value: (T, T, T)) -> Self {
+        Vtx3T {
             x: value.0,
             y: value.1,
             z: value.2,
         }
     }
 }
-impl From<Vtx3f32> for (f32, f32, f32) {
-    fn from(value: Vtx3f32) -> Self {
+impl<T: Float> From<Vtx3T<T>> for (T, T, T) {
+    fn from(value: Vtx3T<T>) -> Self {
         (value.x, value.y, value.z)
     }
 }
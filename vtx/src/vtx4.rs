@@ -0,0 +1,170 @@
+// ====================================
+// ===== Vector 4d
+// ====================================
+
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::{Float, Vtx3T};
+
+/// A homogeneous 3d vector: points have `w = 1`, directions `w = 0`.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Vtx4T<T: Float> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T: Float> Vtx4T<T> {
+    pub fn new() -> Vtx4T<T> {
+        Vtx4T {
+            x: T::ZERO,
+            y: T::ZERO,
+            z: T::ZERO,
+            w: T::ZERO,
+        }
+    }
+    pub fn build(x: T, y: T, z: T, w: T) -> Vtx4T<T> {
+        Vtx4T { x, y, z, w }
+    }
+    /// A position, moved by translations.
+    pub fn from_point(v: Vtx3T<T>) -> Vtx4T<T> {
+        Vtx4T::build(v.x, v.y, v.z, T::ONE)
+    }
+    /// A direction, which translations leave alone.
+    pub fn from_dir(v: Vtx3T<T>) -> Vtx4T<T> {
+        Vtx4T::build(v.x, v.y, v.z, T::ZERO)
+    }
+    pub fn dot(&self, v: Vtx4T<T>) -> T {
+        (self.x * v.x) + (self.y * v.y) + (self.z * v.z) + (self.w * v.w)
+    }
+    pub fn dist(&self, v: Vtx4T<T>) -> T {
+        (*self - v).magn()
+    }
+    pub fn magn(&self) -> T {
+        self.dot(*self).sqrt()
+    }
+    pub fn unit(&self) -> Vtx4T<T> {
+        *self / self.magn()
+    }
+    pub fn xyz(&self) -> Vtx3T<T> {
+        Vtx3T {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+    /// The point after the divide by `w`, as after a projection.
+    pub fn as_point(&self) -> Vtx3T<T> {
+        self.xyz() / self.w
+    }
+}
+// ========== std::ops ==========
+// Addition
+impl<T: Float> Add<Vtx4T<T>> for Vtx4T<T> {
+    type Output = Self;
+    fn add(self, rhs: Vtx4T<T>) -> Self {
+        Vtx4T {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+            w: self.w + rhs.w,
+        }
+    }
+}
+impl<T: Float> Add<T> for Vtx4T<T> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self {
+        Vtx4T {
+            x: self.x + rhs,
+            y: self.y + rhs,
+            z: self.z + rhs,
+            w: self.w + rhs,
+        }
+    }
+}
+// Subtraction
+impl<T: Float> Sub<Vtx4T<T>> for Vtx4T<T> {
+    type Output = Self;
+    fn sub(self, rhs: Vtx4T<T>) -> Self {
+        Vtx4T {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+            w: self.w - rhs.w,
+        }
+    }
+}
+impl<T: Float> Sub<T> for Vtx4T<T> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self {
+        Vtx4T {
+            x: self.x - rhs,
+            y: self.y - rhs,
+            z: self.z - rhs,
+            w: self.w - rhs,
+        }
+    }
+}
+// Multiplication
+impl<T: Float> Mul<Vtx4T<T>> for Vtx4T<T> {
+    type Output = Self;
+    fn mul(self, rhs: Vtx4T<T>) -> Self {
+        Vtx4T {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+            z: self.z * rhs.z,
+            w: self.w * rhs.w,
+        }
+    }
+}
+impl<T: Float> Mul<T> for Vtx4T<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Vtx4T {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+            w: self.w * rhs,
+        }
+    }
+}
+// Division
+impl<T: Float> Div<Vtx4T<T>> for Vtx4T<T> {
+    type Output = Self;
+    fn div(self, rhs: Vtx4T<T>) -> Self {
+        Vtx4T {
+            x: self.x / rhs.x,
+            y: self.y / rhs.y,
+            z: self.z / rhs.z,
+            w: self.w / rhs.w,
+        }
+    }
+}
+impl<T: Float> Div<T> for Vtx4T<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        Vtx4T {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+            w: self.w / rhs,
+        }
+    }
+}
+// ========== From ===========
+impl<T: Float> From<(T, T, T, T)> for Vtx4T<T> {
+    fn from(value: (T, T, T, T)) -> Self {
+        Vtx4T {
+            x: value.0,
+            y: value.1,
+            z: value.2,
+            w: value.3,
+        }
+    }
+}
+impl<T: Float> From<Vtx4T<T>> for (T, T, T, T) {
+    fn from(value: Vtx4T<T>) -> Self {
+        (value.x, value.y, value.z, value.w)
+    }
+}
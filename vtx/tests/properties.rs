@@ -0,0 +1,367 @@
+// ====================================
+// ===== Algebra properties
+// ====================================
+
+//! The same properties for `f32` and `f64`. Entries stay small so `f32`
+//! rounding stays well inside the tolerance.
+
+use proptest::prelude::*;
+
+macro_rules! properties {
+    ($name:ident, $t:ty, $tol:expr) => {
+        mod $name {
+            use super::*;
+            use std::f64::consts::PI;
+            use vtx::{Mtx2T, Mtx3T, Mtx4T, QtxT, Vtx2T, Vtx3T, Vtx4T};
+
+            const TOL: $t = $tol;
+
+            /// Equal up to `TOL`, relative to the size of the numbers.
+            fn close(a: $t, b: $t) -> bool {
+                close_to(a, b, a.abs().max(b.abs()))
+            }
+
+            /// Equal up to `TOL`, relative to `scale`, for results that may
+            /// be much smaller than what went into them.
+            fn close_to(a: $t, b: $t, scale: $t) -> bool {
+                (a - b).abs() <= TOL * (1.0 + scale)
+            }
+
+            fn close2(a: Vtx2T<$t>, b: Vtx2T<$t>) -> bool {
+                close(a.x, b.x) && close(a.y, b.y)
+            }
+
+            fn close3(a: Vtx3T<$t>, b: Vtx3T<$t>) -> bool {
+                close(a.x, b.x) && close(a.y, b.y) && close(a.z, b.z)
+            }
+
+            /// The same rotation: `q` and `-q` both count.
+            fn same_rotation(a: QtxT<$t>, b: QtxT<$t>) -> bool {
+                let b = if a.dot(b) < 0.0 { -b } else { b };
+                close(a.s, b.s) && close3(a.u, b.u)
+            }
+
+            fn close_m2(a: Mtx2T<$t>, b: Mtx2T<$t>) -> bool {
+                close2(a.i(), b.i()) && close2(a.j(), b.j())
+            }
+
+            fn close_m3(a: Mtx3T<$t>, b: Mtx3T<$t>) -> bool {
+                close3(a.i(), b.i()) && close3(a.j(), b.j()) && close3(a.k(), b.k())
+            }
+
+            fn close4(a: Vtx4T<$t>, b: Vtx4T<$t>) -> bool {
+                close3(a.xyz(), b.xyz()) && close(a.w, b.w)
+            }
+
+            fn close_m4(a: Mtx4T<$t>, b: Mtx4T<$t>) -> bool {
+                [(a.i(), b.i()), (a.j(), b.j()), (a.k(), b.k()), (a.l(), b.l())]
+                    .into_iter()
+                    .all(|(a, b)| close4(a, b))
+            }
+
+            fn num() -> impl Strategy<Value = $t> {
+                -2.0 as $t..2.0 as $t
+            }
+
+            fn angle() -> impl Strategy<Value = $t> {
+                -PI as $t..PI as $t
+            }
+
+            fn vtx2() -> impl Strategy<Value = Vtx2T<$t>> {
+                (num(), num()).prop_map(Vtx2T::from)
+            }
+
+            fn vtx3() -> impl Strategy<Value = Vtx3T<$t>> {
+                (num(), num(), num()).prop_map(Vtx3T::from)
+            }
+
+            fn mtx2() -> impl Strategy<Value = Mtx2T<$t>> {
+                (vtx2(), vtx2()).prop_map(|(i, j)| Mtx2T::new().put_i(i).put_j(j))
+            }
+
+            fn mtx3() -> impl Strategy<Value = Mtx3T<$t>> {
+                (vtx3(), vtx3(), vtx3())
+                    .prop_map(|(i, j, k)| Mtx3T::new().put_i(i).put_j(j).put_k(k))
+            }
+
+            fn vtx4() -> impl Strategy<Value = Vtx4T<$t>> {
+                (num(), num(), num(), num()).prop_map(Vtx4T::from)
+            }
+
+            fn mtx4() -> impl Strategy<Value = Mtx4T<$t>> {
+                (vtx4(), vtx4(), vtx4(), vtx4()).prop_map(|(i, j, k, l)| {
+                    Mtx4T::new().put_i(i).put_j(j).put_k(k).put_l(l)
+                })
+            }
+
+            /// A unit quaternion turning by up to a half turn either way.
+            fn rotation() -> impl Strategy<Value = QtxT<$t>> {
+                (vtx3(), angle())
+                    .prop_filter("axis too short", |(axis, _)| axis.magn() > 0.1)
+                    .prop_map(|(axis, angle)| QtxT::from_axis_angle(axis.unit(), angle))
+            }
+
+            proptest! {
+                // ========== Vtx ==========
+                #[test]
+                fn cross3_is_perpendicular(a in vtx3(), b in vtx3()) {
+                    let c = a.cross(b);
+                    prop_assert!(close(c.dot(a), 0.0) && close(c.dot(b), 0.0));
+                }
+
+                #[test]
+                fn rot2_round_trips(t in angle()) {
+                    prop_assert!(close(Vtx2T::from_rot(t).as_rot(), t));
+                }
+
+                // ========== Mtx2 ==========
+                #[test]
+                fn mtx2_combine_is_associative(a in mtx2(), b in mtx2(), c in mtx2()) {
+                    prop_assert!(close_m2((a * b) * c, a * (b * c)));
+                }
+
+                #[test]
+                fn mtx2_combine_applies_in_turn(a in mtx2(), b in mtx2(), v in vtx2()) {
+                    prop_assert!(close2((a * b) * v, a * (b * v)));
+                }
+
+                #[test]
+                fn mtx2_inverse(m in mtx2()) {
+                    prop_assume!(m.det().abs() > 0.25);
+                    let inverse = m.inverse().unwrap();
+                    prop_assert!(close_m2(m * inverse, Mtx2T::ident()));
+                    prop_assert!(close_m2(inverse * m, Mtx2T::ident()));
+                }
+
+                #[test]
+                fn mtx2_det_is_multiplicative(a in mtx2(), b in mtx2()) {
+                    // the Hadamard bound: no determinant is larger
+                    let scale = a.i().magn() * a.j().magn() * b.i().magn() * b.j().magn();
+                    prop_assert!(close_to((a * b).det(), a.det() * b.det(), scale));
+                }
+
+                #[test]
+                fn mtx2_rotation_keeps_length(t in angle(), v in vtx2()) {
+                    prop_assert!(close((Mtx2T::from_rot(t) * v).magn(), v.magn()));
+                }
+
+                #[test]
+                fn mtx2_rows_and_columns(m in mtx2(), v in vtx2()) {
+                    prop_assert_eq!(m.put_x(v).x(), v);
+                    prop_assert_eq!(m.put_y(v).y(), v);
+                    prop_assert_eq!(m.put_x(v).y(), m.y());
+                    prop_assert_eq!(m.transpose().i(), m.x());
+                    prop_assert_eq!(m.transpose().transpose(), m);
+                }
+
+                // ========== Mtx3 ==========
+                #[test]
+                fn mtx3_combine_is_associative(a in mtx3(), b in mtx3(), c in mtx3()) {
+                    prop_assert!(close_m3((a * b) * c, a * (b * c)));
+                }
+
+                #[test]
+                fn mtx3_combine_applies_in_turn(a in mtx3(), b in mtx3(), v in vtx3()) {
+                    prop_assert!(close3((a * b) * v, a * (b * v)));
+                }
+
+                #[test]
+                fn mtx3_inverse(m in mtx3()) {
+                    prop_assume!(m.det().abs() > 0.5);
+                    let inverse = m.inverse().unwrap();
+                    prop_assert!(close_m3(m * inverse, Mtx3T::ident()));
+                    prop_assert!(close_m3(inverse * m, Mtx3T::ident()));
+                }
+
+                #[test]
+                fn mtx3_det_is_multiplicative(a in mtx3(), b in mtx3()) {
+                    let scale = [a.i(), a.j(), a.k(), b.i(), b.j(), b.k()]
+                        .iter()
+                        .map(|v| v.magn())
+                        .product();
+                    prop_assert!(close_to((a * b).det(), a.det() * b.det(), scale));
+                }
+
+                #[test]
+                fn mtx3_rotation_keeps_length(t in angle(), v in vtx3()) {
+                    for rot in [Mtx3T::from_rot_x(t), Mtx3T::from_rot_y(t), Mtx3T::from_rot_z(t)] {
+                        prop_assert!(close((rot * v).magn(), v.magn()));
+                        prop_assert!(close(rot.det(), 1.0));
+                    }
+                }
+
+                #[test]
+                fn mtx3_rows_and_columns(m in mtx3(), v in vtx3()) {
+                    prop_assert_eq!(m.put_x(v).x(), v);
+                    prop_assert_eq!(m.put_y(v).y(), v);
+                    prop_assert_eq!(m.put_z(v).z(), v);
+                    prop_assert_eq!(m.transpose().i(), m.x());
+                    prop_assert_eq!(m.transpose().transpose(), m);
+                }
+
+                // ========== Mtx4 ==========
+                #[test]
+                fn mtx4_combine_is_associative(a in mtx4(), b in mtx4(), c in mtx4()) {
+                    prop_assert!(close_m4((a * b) * c, a * (b * c)));
+                }
+
+                #[test]
+                fn mtx4_combine_applies_in_turn(a in mtx4(), b in mtx4(), v in vtx4()) {
+                    prop_assert!(close4((a * b) * v, a * (b * v)));
+                }
+
+                #[test]
+                fn mtx4_inverse(m in mtx4()) {
+                    prop_assume!(m.det().abs() > 1.0);
+                    let inverse = m.inverse().unwrap();
+                    prop_assert!(close_m4(m * inverse, Mtx4T::ident()));
+                    prop_assert!(close_m4(inverse * m, Mtx4T::ident()));
+                }
+
+                #[test]
+                fn mtx4_det_is_multiplicative(a in mtx4(), b in mtx4()) {
+                    let scale = [a.i(), a.j(), a.k(), a.l(), b.i(), b.j(), b.k(), b.l()]
+                        .iter()
+                        .map(|v| v.magn())
+                        .product();
+                    prop_assert!(close_to((a * b).det(), a.det() * b.det(), scale));
+                }
+
+                #[test]
+                fn mtx4_rows_and_columns(m in mtx4(), v in vtx4()) {
+                    prop_assert_eq!(m.put_x(v).x(), v);
+                    prop_assert_eq!(m.put_w(v).w(), v);
+                    prop_assert_eq!(m.put_l(v).l(), v);
+                    prop_assert_eq!(m.transpose().i(), m.x());
+                    prop_assert_eq!(m.transpose().transpose(), m);
+                }
+
+                #[test]
+                fn mtx4_translation_moves_points_only(t in vtx3(), v in vtx3()) {
+                    let m = Mtx4T::from_translation(t);
+                    prop_assert!(close3(m.apply_point(v), v + t));
+                    prop_assert_eq!(m.apply_dir(v), v);
+                }
+
+                #[test]
+                fn mtx4_wraps_the_3d_maps(m in mtx3(), q in rotation(), s in vtx3(), v in vtx3()) {
+                    prop_assert!(close3(Mtx4T::from(m).apply_point(v), m * v));
+                    prop_assert!(close3(Mtx4T::from(q).apply_dir(v), q * v));
+                    prop_assert!(close3(Mtx4T::from_scale(s).apply_point(v), s * v));
+                }
+
+                #[test]
+                fn mtx4_look_at(eye in vtx3(), target in vtx3(), up in vtx3()) {
+                    let view = (target - eye).unit();
+                    prop_assume!((target - eye).magn() > 0.1 && up.magn() > 0.1);
+                    prop_assume!(view.cross(up.unit()).magn() > 0.1);
+                    let m = Mtx4T::look_at(eye, target, up);
+                    let distance = (target - eye).magn();
+                    prop_assert!(close3(m.apply_point(eye), Vtx3T::new()));
+                    prop_assert!(close3(m.apply_point(target), Vtx3T::build(0.0, 0.0, -distance)));
+                    // up stays on the upper half of the screen
+                    prop_assert!(m.apply_dir(up).y > 0.0);
+                    prop_assert!(close(m.det(), 1.0));
+                }
+
+                #[test]
+                fn mtx4_perspective(fov in 0.2 as $t..3.0 as $t, aspect in 0.5 as $t..2.0 as $t, x in num(), y in num()) {
+                    let (near, far) = (0.5, 20.0);
+                    let m = Mtx4T::perspective(fov, aspect, near, far);
+                    prop_assert!(close(m.apply_point(Vtx3T::build(x, y, -near)).z, -1.0));
+                    prop_assert!(close(m.apply_point(Vtx3T::build(x, y, -far)).z, 1.0));
+                    // the top edge of the view lands on the top of the screen
+                    let top = Vtx3T::build(0.0, (fov / 2.0).tan() * 5.0, -5.0);
+                    prop_assert!(close(m.apply_point(top).y, 1.0));
+                    let right = Vtx3T::build((fov / 2.0).tan() * aspect * 5.0, 0.0, -5.0);
+                    prop_assert!(close(m.apply_point(right).x, 1.0));
+                }
+
+                #[test]
+                fn mtx4_orthographic(a in vtx3(), b in vtx3()) {
+                    prop_assume!((a.x - b.x).abs() > 0.1 && (a.y - b.y).abs() > 0.1 && (a.z - b.z).abs() > 0.1);
+                    let m = Mtx4T::orthographic(a.x, b.x, a.y, b.y, a.z, b.z);
+                    let corner = Vtx3T::build(-1.0, -1.0, -1.0);
+                    // near and far are distances along -z
+                    prop_assert!(close3(m.apply_point(Vtx3T::build(a.x, a.y, -a.z)), corner));
+                    prop_assert!(close3(m.apply_point(Vtx3T::build(b.x, b.y, -b.z)), corner * -1.0));
+                }
+
+                // ========== Qtx ==========
+                #[test]
+                fn qtx_rotation_keeps_length(axis in vtx3(), t in angle(), v in vtx3()) {
+                    prop_assume!(axis.magn() > 0.1);
+                    let q = QtxT::from_axis_angle(axis.unit(), t);
+                    prop_assert!(close(q.apply(v).magn(), v.magn()));
+                }
+
+                #[test]
+                fn qtx_matches_the_matrices(t in angle(), v in vtx3()) {
+                    let x = QtxT::from_axis_angle(Vtx3T::build(1.0, 0.0, 0.0), t);
+                    let y = QtxT::from_axis_angle(Vtx3T::build(0.0, 1.0, 0.0), t);
+                    let z = QtxT::from_axis_angle(Vtx3T::build(0.0, 0.0, 1.0), t);
+                    prop_assert!(close3(x.apply(v), Mtx3T::from_rot_x(t) * v));
+                    prop_assert!(close3(y.apply(v), Mtx3T::from_rot_y(t) * v));
+                    prop_assert!(close3(z.apply(v), Mtx3T::from_rot_z(t) * v));
+                }
+
+                #[test]
+                fn qtx_combine_applies_in_turn(a in rotation(), b in rotation(), v in vtx3()) {
+                    prop_assert!(close3((a * b) * v, a * (b * v)));
+                    prop_assert!(close((a * b).magn(), 1.0));
+                }
+
+                #[test]
+                fn qtx_combine_is_associative(a in rotation(), b in rotation(), c in rotation()) {
+                    prop_assert!(same_rotation((a * b) * c, a * (b * c)));
+                }
+
+                #[test]
+                fn qtx_inverse(q in rotation(), v in vtx3()) {
+                    let inverse = q.inverse().unwrap();
+                    prop_assert!(same_rotation(q * inverse, QtxT::ident()));
+                    prop_assert!(same_rotation(inverse, q.conj()));
+                    prop_assert!(close3(inverse * (q * v), v));
+                }
+
+                #[test]
+                fn qtx_axis_angle_round_trips(q in rotation()) {
+                    let (axis, angle) = q.to_axis_angle();
+                    prop_assert!(close(axis.magn(), 1.0));
+                    prop_assert!(same_rotation(QtxT::from_axis_angle(axis, angle), q));
+                    prop_assert!(same_rotation(q.put_angle(angle), q));
+                    prop_assert!(same_rotation(q.put_axis(axis), q));
+                }
+
+                #[test]
+                fn qtx_matrix_round_trips(q in rotation(), v in vtx3()) {
+                    let m = Mtx3T::from(q);
+                    prop_assert!(close3(m * v, q * v));
+                    prop_assert!(close(m.det(), 1.0));
+                    prop_assert!(same_rotation(QtxT::from(m), q));
+                }
+
+                #[test]
+                fn qtx_interpolation(a in rotation(), b in rotation(), t in 0.0 as $t..1.0 as $t) {
+                    prop_assert!(same_rotation(a.slerp(b, 0.0), a));
+                    prop_assert!(same_rotation(a.slerp(b, 1.0), b));
+                    prop_assert!(same_rotation(a.nlerp(b, 1.0), b));
+                    prop_assert!(close(a.slerp(b, t).magn(), 1.0));
+                    prop_assert!(close(a.nlerp(b, t).magn(), 1.0));
+                }
+
+                #[test]
+                fn qtx_slerp_turns_evenly(q in rotation(), t in 0.0 as $t..1.0 as $t) {
+                    // from no rotation, a fraction of the way is that fraction of the angle
+                    let (axis, angle) = q.to_axis_angle();
+                    prop_assume!(angle < 3.0);
+                    let part = QtxT::ident().slerp(q, t);
+                    prop_assert!(same_rotation(part, QtxT::from_axis_angle(axis, angle * t)));
+                }
+            }
+        }
+    };
+}
+
+properties!(f32s, f32, 1e-4);
+properties!(f64s, f64, 1e-10);